@@ -0,0 +1,24 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Decoding arbitrary strings has to agree with the `hex` crate, including
+// the reported errors.
+fuzz_target!(|s: &str| {
+    let expected = hex::decode(s);
+
+    assert_eq!(easy_hex::decode(s, <[u8]>::to_vec), expected);
+    assert_eq!(easy_hex::decode_into::<Vec<u8>>(s), expected);
+    assert_eq!(
+        easy_hex::decode_into::<Box<[u8]>>(s),
+        expected.clone().map(Vec::into_boxed_slice)
+    );
+
+    let mut array = [0; 16];
+    let expected_array = hex::decode_to_slice(s, &mut array).map(|()| array);
+    assert_eq!(easy_hex::decode_into::<[u8; 16]>(s), expected_array);
+
+    if let Ok(bytes) = expected {
+        assert_eq!(easy_hex::encode(&bytes, str::to_owned), s.to_lowercase());
+    }
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use easy_hex::{Hex, UpperHex};
+use libfuzzer_sys::fuzz_target;
+
+// Encoding has to agree with the `hex` crate, and decode back to the input.
+fuzz_target!(|data: &[u8]| {
+    let lower = easy_hex::encode(data, str::to_owned);
+    assert_eq!(lower, hex::encode(data));
+    assert_eq!(Hex(data).to_string(), lower);
+
+    let upper = easy_hex::encode_upper(data, str::to_owned);
+    assert_eq!(upper, hex::encode_upper(data));
+    assert_eq!(UpperHex(data).to_string(), upper);
+
+    assert_eq!(easy_hex::decode(&lower, <[u8]>::to_vec).unwrap(), data);
+    assert_eq!(easy_hex::decode_into::<Vec<u8>>(&upper).unwrap(), data);
+});
@@ -0,0 +1,23 @@
+#![no_main]
+
+use easy_hex::{Hex, UpperHex};
+use libfuzzer_sys::fuzz_target;
+
+// `FromStr` has to agree with the `hex` crate, and display the input again.
+fuzz_target!(|s: &str| {
+    let expected = hex::decode(s);
+
+    let hex = s.parse::<Hex<Vec<u8>>>();
+    assert_eq!(hex.clone().map(|h| h.0), expected);
+    let upper = s.parse::<UpperHex<Vec<u8>>>();
+    assert_eq!(upper.clone().map(|h| h.0), expected);
+
+    if let (Ok(hex), Ok(upper)) = (hex, upper) {
+        assert_eq!(hex.to_string(), s.to_lowercase());
+        assert_eq!(upper.to_string(), s.to_uppercase());
+    }
+
+    let mut array = [0; 4];
+    let expected_array = hex::decode_to_slice(s, &mut array).map(|()| Hex(array));
+    assert_eq!(s.parse::<Hex<[u8; 4]>>(), expected_array);
+});
@@ -0,0 +1,27 @@
+#![no_main]
+
+use easy_hex::{Hex, LenientHex, UpperHex};
+use libfuzzer_sys::fuzz_target;
+
+// Serialization has to produce the same strings as the `hex` crate, and
+// deserializing arbitrary JSON must never panic.
+fuzz_target!(|data: &[u8]| {
+    let json = serde_json::to_string(&Hex(data)).unwrap();
+    assert_eq!(json, format!("\"{}\"", hex::encode(data)));
+    assert_eq!(serde_json::from_str::<Hex<Vec<u8>>>(&json).unwrap().0, data);
+
+    let json = serde_json::to_string(&UpperHex(data)).unwrap();
+    assert_eq!(json, format!("\"{}\"", hex::encode_upper(data)));
+    assert_eq!(serde_json::from_str::<UpperHex<Vec<u8>>>(&json).unwrap().0, data);
+
+    if let Ok(s) = std::str::from_utf8(data) {
+        let hex = serde_json::from_str::<Hex<Vec<u8>>>(s);
+        let _ = serde_json::from_str::<Hex<[u8; 4]>>(s);
+        let _ = serde_json::from_str::<LenientHex<Vec<u8>>>(s);
+
+        // a JSON string decodes exactly like the `hex` crate decodes it
+        if let Ok(string) = serde_json::from_str::<String>(s) {
+            assert_eq!(hex.ok().map(|h| h.0), hex::decode(string).ok());
+        }
+    }
+});
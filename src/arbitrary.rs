@@ -0,0 +1,46 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::{Hex, UpperHex};
+
+macro_rules! impl_arbitrary {
+    ($Hex:ident) => {
+        impl<'a, T> Arbitrary<'a> for $Hex<T>
+        where
+            T: Arbitrary<'a>,
+        {
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                T::arbitrary(u).map($Hex)
+            }
+
+            fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+                T::arbitrary_take_rest(u).map($Hex)
+            }
+
+            fn size_hint(depth: usize) -> (usize, Option<usize>) {
+                T::size_hint(depth)
+            }
+        }
+    };
+}
+
+impl_arbitrary!(Hex);
+impl_arbitrary!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arbitrary() {
+        let data = [1, 0x99, 0xff, 3];
+
+        let hex = Hex::<[u8; 3]>::arbitrary(&mut Unstructured::new(&data)).unwrap();
+        assert_eq!(hex, Hex([1, 0x99, 0xff]));
+
+        let hex = UpperHex::<Vec<u8>>::arbitrary_take_rest(Unstructured::new(&data)).unwrap();
+        let vec = Vec::<u8>::arbitrary_take_rest(Unstructured::new(&data)).unwrap();
+        assert_eq!(hex, UpperHex(vec));
+
+        assert_eq!(Hex::<[u8; 3]>::size_hint(0), (3, Some(3)));
+    }
+}
@@ -0,0 +1,115 @@
+//! Command line interface for encoding, decoding and dumping hex.
+
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::PathBuf,
+    process::ExitCode,
+};
+
+use clap::{Args, Parser, Subcommand};
+use easy_hex::{DecodeWriter, DumpWriter, EncodeWriter};
+
+#[derive(Parser)]
+#[command(version, about)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Encode binary input as hex
+    Encode {
+        #[command(flatten)]
+        input: Input,
+        /// Write uppercase hex
+        #[arg(short, long)]
+        upper: bool,
+        /// Prefix written before every byte, like `0x`
+        #[arg(short, long, default_value = "")]
+        prefix: String,
+        /// Separator written between bytes
+        #[arg(short, long, default_value = "")]
+        separator: String,
+        /// Number of bytes per line, 0 disables wrapping
+        #[arg(short, long, default_value_t = 0)]
+        wrap: usize,
+    },
+    /// Decode hex input to binary
+    ///
+    /// Whitespace, the separators `,:-_` and `0x` prefixes are skipped.
+    Decode {
+        #[command(flatten)]
+        input: Input,
+    },
+    /// Print an `xxd` style hex dump of binary input
+    Dump {
+        #[command(flatten)]
+        input: Input,
+        /// Write uppercase hex
+        #[arg(short, long)]
+        upper: bool,
+    },
+}
+
+#[derive(Args)]
+struct Input {
+    /// Input file, reads from stdin if missing or `-`
+    file: Option<PathBuf>,
+}
+
+impl Input {
+    fn open(&self) -> io::Result<Box<dyn Read>> {
+        Ok(match &self.file {
+            Some(path) if path.as_os_str() != "-" => Box::new(BufReader::new(File::open(path)?)),
+            _ => Box::new(io::stdin().lock()),
+        })
+    }
+}
+
+fn run(cli: Cli) -> io::Result<()> {
+    let stdout = BufWriter::new(io::stdout().lock());
+    match cli.command {
+        Command::Encode {
+            input,
+            upper,
+            prefix,
+            separator,
+            wrap,
+        } => {
+            let mut encoder = EncodeWriter::new(stdout)
+                .upper(upper)
+                .prefix(&prefix)
+                .separator(&separator)
+                .wrap(wrap);
+            let n = io::copy(&mut input.open()?, &mut encoder)?;
+            let mut stdout = encoder.finish()?;
+            if n > 0 {
+                writeln!(stdout)?;
+            }
+            stdout.flush()
+        }
+        Command::Decode { input } => {
+            let mut decoder = DecodeWriter::new(stdout).lenient(true);
+            io::copy(&mut input.open()?, &mut decoder)?;
+            decoder.finish()?.flush()
+        }
+        Command::Dump { input, upper } => {
+            let mut dump = DumpWriter::new(stdout).upper(upper);
+            io::copy(&mut input.open()?, &mut dump)?;
+            dump.finish()?.flush()
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Cli::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("easy-hex: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
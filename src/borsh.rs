@@ -0,0 +1,58 @@
+use ::borsh::io::{Read, Result, Write};
+
+use ::borsh::{BorshDeserialize, BorshSerialize};
+
+use crate::{Hex, UpperHex};
+
+macro_rules! impl_borsh {
+    ($Hex:ident) => {
+        impl<T> BorshSerialize for $Hex<T>
+        where
+            T: BorshSerialize + ?Sized,
+        {
+            fn serialize<W: Write>(&self, writer: &mut W) -> Result<()> {
+                self.0.serialize(writer)
+            }
+        }
+
+        impl<T> BorshDeserialize for $Hex<T>
+        where
+            T: BorshDeserialize,
+        {
+            fn deserialize_reader<R: Read>(reader: &mut R) -> Result<Self> {
+                T::deserialize_reader(reader).map($Hex)
+            }
+        }
+    };
+}
+
+impl_borsh!(Hex);
+impl_borsh!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    struct Transfer {
+        to: Hex<[u8; 4]>,
+        memo: UpperHex<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_transparent() {
+        let transfer = Transfer {
+            to: Hex([1, 0x99, 0xff, 0]),
+            memo: UpperHex(vec![0xab, 0xcd]),
+        };
+        let bytes = ::borsh::to_vec(&transfer).unwrap();
+        assert_eq!(bytes, [1, 0x99, 0xff, 0, 2, 0, 0, 0, 0xab, 0xcd]);
+        assert_eq!(
+            bytes,
+            ::borsh::to_vec(&([1_u8, 0x99, 0xff, 0], vec![0xab_u8, 0xcd])).unwrap()
+        );
+        assert_eq!(::borsh::from_slice::<Transfer>(&bytes).unwrap(), transfer);
+
+        assert!(::borsh::from_slice::<Hex<[u8; 4]>>(&[1, 2, 3]).is_err());
+    }
+}
@@ -0,0 +1,134 @@
+use alloc::string::String;
+
+use bytes::{Bytes, BytesMut};
+use hex::FromHexError;
+
+use crate::{decode::decode_in_place, decode_into, FromHexBytes};
+
+impl FromHexBytes for BytesMut {
+    type Buffer = BytesMut;
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        Some(BytesMut::zeroed(len))
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer)
+    }
+}
+
+impl FromHexBytes for Bytes {
+    type Buffer = BytesMut;
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        Some(BytesMut::zeroed(len))
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer.freeze())
+    }
+
+    fn from_hex_string(v: String) -> Result<Self, String> {
+        decode_in_place(v).map(Bytes::from)
+    }
+}
+
+/// Decodes a hex string directly into a `BytesMut`.
+///
+/// This accepts both lower and upper case strings.
+/// No intermediate buffer is used.
+pub fn decode_bytes_mut(v: &str) -> Result<BytesMut, FromHexError> {
+    decode_into(v)
+}
+
+/// Decodes a hex string directly into a `Bytes`.
+///
+/// This accepts both lower and upper case strings.
+/// No intermediate buffer is used.
+pub fn decode_bytes(v: &str) -> Result<Bytes, FromHexError> {
+    decode_into(v)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode_bytes("0199fF").unwrap(), [1, 0x99, 0xff][..]);
+        assert_eq!(decode_bytes_mut("0199fF").unwrap(), [1, 0x99, 0xff][..]);
+        assert_eq!(decode_bytes("").unwrap(), Bytes::new());
+        assert_eq!(decode_bytes("019"), Err(FromHexError::OddLength));
+        assert_eq!(
+            decode_bytes_mut("01zz"),
+            Err(FromHexError::InvalidHexCharacter { c: 'z', index: 2 })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use serde_derive::{Deserialize, Serialize};
+
+        use crate::{
+            tests::{from_json, to_json},
+            Hex, UpperHex,
+        };
+
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Test {
+            #[serde(with = "crate::serde")]
+            a: Bytes,
+            #[serde(with = "crate::serde_upper")]
+            b: BytesMut,
+            c: Hex<Bytes>,
+            d: UpperHex<BytesMut>,
+        }
+
+        let test = Test {
+            a: Bytes::from_static(&[1, 0x99, 0xff]),
+            b: BytesMut::from(&[0xab, 0xcd][..]),
+            c: Hex(Bytes::from(vec![0xef; 100])),
+            d: UpperHex(BytesMut::new()),
+        };
+        let json = serde_json::to_string(&test).unwrap();
+        assert_eq!(
+            json,
+            format!(
+                r#"{{"a":"0199ff","b":"ABCD","c":"{}","d":""}}"#,
+                "ef".repeat(100)
+            )
+        );
+        assert_eq!(serde_json::from_str::<Test>(&json).unwrap(), test);
+
+        let json = r#"{"a":"019","b":"","c":"","d":""}"#;
+        assert!(serde_json::from_str::<Test>(json).is_err());
+
+        let hex = Hex(Bytes::from_static(&[1, 0x99, 0xff]));
+        assert_eq!(to_json(&hex), r#"{"data":"0199ff"}"#);
+        assert_eq!(from_json::<Hex<Bytes>>(r#"{"data":"0199FF"}"#), hex);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_owned() {
+        use serde::{
+            de::{value::Error, IntoDeserializer},
+            Deserialize,
+        };
+
+        use crate::Hex;
+
+        let s = String::from("0199fF");
+        let ptr = s.as_ptr();
+        let hex: Result<Hex<Bytes>, Error> = Hex::deserialize(s.into_deserializer());
+        let hex = hex.unwrap();
+        assert_eq!(hex.0, [1, 0x99, 0xff][..]);
+        // decoded in the allocation of the string
+        assert_eq!(hex.0.as_ptr(), ptr);
+
+        let hex: Result<Hex<BytesMut>, Error> =
+            Hex::deserialize(String::from("0199fF").into_deserializer());
+        assert_eq!(hex.unwrap().0, [1, 0x99, 0xff][..]);
+    }
+}
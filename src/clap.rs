@@ -0,0 +1,238 @@
+use std::{ffi::OsStr, marker::PhantomData};
+
+use clap::{
+    builder::{MapValueParser, TypedValueParser, ValueParserFactory},
+    error::ErrorKind,
+    Arg, Command, Error,
+};
+
+use crate::{decode_into, FromHexBytes, FromHexError, Hex, UpperHex};
+
+/// Parser for hex encoded command line arguments.
+///
+/// This is the value parser of `Hex<T>` and `UpperHex<T>`, so they can be
+/// used directly as argument types. It can also be used on its own, to
+/// parse arguments into the container `T`. Errors point to the invalid
+/// character, or state the expected length of fixed size containers.
+///
+/// ```rust
+/// use clap::{value_parser, Arg, Command};
+/// use easy_hex::{ArgExt, Hex};
+///
+/// let cmd = Command::new("tool").arg(
+///     Arg::new("key")
+///         .long("key")
+///         .value_parser(value_parser!(Hex<[u8; 4]>))
+///         .hex(),
+/// );
+///
+/// let matches = cmd.clone().get_matches_from(["tool", "--key", "0199ff00"]);
+/// assert_eq!(matches.get_one::<Hex<[u8; 4]>>("key"), Some(&Hex([1, 0x99, 0xff, 0])));
+///
+/// let err = cmd.try_get_matches_from(["tool", "--key", "0199"]).unwrap_err();
+/// assert!(err.to_string().contains("expected 4 bytes (8 hex digits), found 2 bytes"));
+/// ```
+///
+/// Note that clap does not let value parsers influence the help output. Use
+/// [`ArgExt::hex`] to show the argument as hex string in `--help`.
+pub struct HexValueParser<T>(PhantomData<fn() -> T>);
+
+impl<T> HexValueParser<T> {
+    /// Creates a new parser.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T> Default for HexValueParser<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for HexValueParser<T> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+/// Extension of `clap::Arg` for hex encoded arguments.
+pub trait ArgExt {
+    /// Shows the argument as hex string in the help output.
+    ///
+    /// This sets the value name to `HEX`, and the help text to `Hex encoded
+    /// bytes` if the argument has none. With the derive API, it can be used
+    /// as `#[arg(long, hex())]`.
+    fn hex(self) -> Self;
+}
+
+impl ArgExt for Arg {
+    fn hex(self) -> Self {
+        let arg = self.value_name("HEX");
+        match arg.get_help() {
+            Some(_) => arg,
+            None => arg.help("Hex encoded bytes"),
+        }
+    }
+}
+
+fn describe_error<T: FromHexBytes>(s: &str, err: FromHexError) -> String {
+    match err {
+        FromHexError::InvalidHexCharacter { c, index } => {
+            format!("invalid character '{c}' at position {index}")
+        }
+        FromHexError::OddLength => format!("odd number of hex digits ({})", s.len()),
+        FromHexError::InvalidStringLength => match T::expected_len() {
+            Some(n) => format!(
+                "expected {n} bytes ({} hex digits), found {} bytes",
+                n * 2,
+                s.len() / 2
+            ),
+            None => format!("unsupported length of {} bytes", s.len() / 2),
+        },
+    }
+}
+
+impl<T> TypedValueParser for HexValueParser<T>
+where
+    T: FromHexBytes + Clone + Send + Sync + 'static,
+{
+    type Value = T;
+
+    fn parse_ref(&self, cmd: &Command, arg: Option<&Arg>, value: &OsStr) -> Result<T, Error> {
+        let arg = arg.map_or_else(|| "...".to_owned(), Arg::to_string);
+        let invalid = |reason: String| {
+            let value = value.to_string_lossy();
+            let message = format!("invalid value '{value}' for '{arg}': {reason}\n");
+            Error::raw(ErrorKind::ValueValidation, message).with_cmd(cmd)
+        };
+
+        let s = value
+            .to_str()
+            .ok_or_else(|| invalid("value is not valid UTF-8".to_owned()))?;
+        decode_into(s).map_err(|err| invalid(describe_error::<T>(s, err)))
+    }
+}
+
+macro_rules! impl_clap {
+    ($Hex:ident) => {
+        impl<T> ValueParserFactory for $Hex<T>
+        where
+            T: FromHexBytes + Clone + Send + Sync + 'static,
+        {
+            type Parser = MapValueParser<HexValueParser<T>, fn(T) -> Self>;
+
+            fn value_parser() -> Self::Parser {
+                HexValueParser::new().map($Hex)
+            }
+        }
+    };
+}
+
+impl_clap!(Hex);
+impl_clap!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use clap::value_parser;
+
+    use super::*;
+
+    fn cmd() -> Command {
+        Command::new("tool")
+            .arg(
+                Arg::new("key")
+                    .long("key")
+                    .value_parser(value_parser!(Hex<[u8; 4]>)),
+            )
+            .arg(
+                Arg::new("id")
+                    .long("id")
+                    .value_parser(value_parser!(Hex<[u8; 2]>))
+                    .hex(),
+            )
+            .arg(
+                Arg::new("mac")
+                    .long("mac")
+                    .help("MAC of the message")
+                    .value_parser(value_parser!(UpperHex<[u8; 6]>))
+                    .hex(),
+            )
+            .arg(
+                Arg::new("data")
+                    .long("data")
+                    .value_parser(value_parser!(UpperHex<Vec<u8>>)),
+            )
+            .arg(
+                Arg::new("raw")
+                    .long("raw")
+                    .value_parser(HexValueParser::<Vec<u8>>::new()),
+            )
+    }
+
+    fn error(args: &[&str]) -> String {
+        let args = ["tool"].iter().chain(args);
+        let err = cmd().try_get_matches_from(args).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ValueValidation);
+        err.to_string()
+    }
+
+    #[test]
+    fn test_parse() {
+        let matches = cmd()
+            .try_get_matches_from(["tool", "--key", "0199FF00", "--data", "", "--raw", "ab"])
+            .unwrap();
+        assert_eq!(
+            matches.get_one::<Hex<[u8; 4]>>("key"),
+            Some(&Hex([1, 0x99, 0xff, 0]))
+        );
+        assert_eq!(
+            matches.get_one::<UpperHex<Vec<u8>>>("data"),
+            Some(&UpperHex(vec![]))
+        );
+        assert_eq!(matches.get_one::<Vec<u8>>("raw"), Some(&vec![0xab]));
+    }
+
+    #[test]
+    fn test_help() {
+        let help = cmd().render_help().to_string();
+        assert!(help.contains("--id <HEX>"), "{help}");
+        assert!(help.contains("Hex encoded bytes"), "{help}");
+        assert!(help.contains("--mac <HEX>"), "{help}");
+        assert!(help.contains("MAC of the message"), "{help}");
+        assert!(help.contains("--key <key>"), "{help}");
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_help_derive() {
+        use clap::{CommandFactory, Parser};
+
+        #[derive(Parser)]
+        struct Args {
+            /// Key to sign with
+            #[arg(long, hex())]
+            key: Hex<[u8; 4]>,
+        }
+
+        let help = Args::command().render_help().to_string();
+        assert!(help.contains("--key <HEX>  Key to sign with"), "{help}");
+
+        let args = Args::parse_from(["tool", "--key", "0199ff00"]);
+        assert_eq!(args.key, Hex([1, 0x99, 0xff, 0]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = error(&["--key", "0199zz00"]);
+        assert!(err.starts_with(
+            "error: invalid value '0199zz00' for '--key <key>': invalid character 'z' at position 4\n"
+        ));
+
+        let err = error(&["--key", "0199ff"]);
+        assert!(err.contains("expected 4 bytes (8 hex digits), found 3 bytes"));
+
+        let err = error(&["--data", "019"]);
+        assert!(err.contains("for '--data <data>': odd number of hex digits (3)"));
+    }
+}
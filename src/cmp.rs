@@ -0,0 +1,118 @@
+use crate::{
+    encode::{byte2hex, HEX_CHARS_LOWER, HEX_CHARS_UPPER},
+    Hex, UpperHex, LOWER, UPPER,
+};
+
+/// Compares bytes with a hex string by encoding them one byte at a time.
+fn eq_str(bytes: &[u8], s: &str, eq: impl Fn(u8, u8) -> bool, table: &[u8; 16]) -> bool {
+    let s = s.as_bytes();
+    s.len() == bytes.len() * 2
+        && bytes.iter().zip(s.chunks_exact(2)).all(|(&byte, digits)| {
+            let (high, low) = byte2hex(byte, table);
+            eq(digits[0], high) && eq(digits[1], low)
+        })
+}
+
+/// Compares bytes with a hex string of either case.
+fn eq_str_ignore_case(bytes: &[u8], s: &str) -> bool {
+    eq_str(bytes, s, |a, b| a.eq_ignore_ascii_case(&b), HEX_CHARS_LOWER)
+}
+
+/// Compares bytes with a hex string of the given case.
+fn eq_str_strict<const U: bool>(bytes: &[u8], s: &str) -> bool {
+    let table = if U { HEX_CHARS_UPPER } else { HEX_CHARS_LOWER };
+    eq_str(bytes, s, |a, b| a == b, table)
+}
+
+macro_rules! impl_eq_str {
+    ($Hex:ident, $case:ident, $case_name:literal) => {
+        impl<T> $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            /// Compares the bytes with a hex string of either case, without
+            /// allocating.
+            pub fn eq_str(&self, s: &str) -> bool {
+                eq_str_ignore_case(self.0.as_ref(), s)
+            }
+
+            #[doc = concat!("Compares the bytes with a ", $case_name, " hex string.")]
+            ///
+            /// Unlike `eq_str`, this only accepts the case the wrapper is
+            /// formatted in. Neither allocates.
+            pub fn eq_str_strict(&self, s: &str) -> bool {
+                eq_str_strict::<$case>(self.0.as_ref(), s)
+            }
+        }
+    };
+}
+
+impl_eq_str!(Hex, LOWER, "lowercase");
+impl_eq_str!(UpperHex, UPPER, "uppercase");
+
+#[cfg(feature = "serde")]
+mod lenient {
+    use super::*;
+    use crate::LenientHex;
+
+    impl_eq_str!(LenientHex, LOWER, "lowercase");
+}
+
+#[cfg(feature = "subtle")]
+mod ct {
+    use crate::{subtle::ct_eq_str, CtHex};
+
+    impl<T> CtHex<T>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        /// Compares the bytes with a hex string of either case in constant
+        /// time, like `ct_eq_str`.
+        pub fn eq_str(&self, s: &str) -> bool {
+            ct_eq_str(self.0.as_ref(), s).into()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eq_str() {
+        let hex = Hex([1_u8, 0x99, 0xff]);
+        assert!(hex.eq_str("0199ff"));
+        assert!(hex.eq_str("0199FF"));
+        assert!(hex.eq_str("0199Ff"));
+        assert!(!hex.eq_str("0199fe"));
+        assert!(!hex.eq_str("0199f"));
+        assert!(!hex.eq_str("0199ff00"));
+        assert!(!hex.eq_str("0x0199ff"));
+        assert!(Hex(&[][..]).eq_str(""));
+
+        let upper = UpperHex(vec![0xab_u8, 0xcd]);
+        assert!(upper.eq_str("abcd"));
+        assert!(upper.eq_str("ABCD"));
+    }
+
+    #[test]
+    fn test_eq_str_strict() {
+        let hex = Hex([1_u8, 0x99, 0xff]);
+        assert!(hex.eq_str_strict("0199ff"));
+        assert!(!hex.eq_str_strict("0199FF"));
+        assert!(!hex.eq_str_strict("0199Ff"));
+
+        let upper = UpperHex([0xab_u8, 0xcd]);
+        assert!(upper.eq_str_strict("ABCD"));
+        assert!(!upper.eq_str_strict("abcd"));
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn test_ct_eq_str() {
+        let hex = crate::CtHex([1_u8, 0x99, 0xff]);
+        assert!(hex.eq_str("0199Ff"));
+        assert!(!hex.eq_str("0199fe"));
+        assert!(!hex.eq_str("0199"));
+    }
+}
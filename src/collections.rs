@@ -0,0 +1,255 @@
+use core::{fmt, iter, marker::PhantomData};
+
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize,
+};
+
+use crate::{FromHexBytes, Hex};
+
+pub(crate) struct SeqVis<C, T>(pub(crate) PhantomData<(C, T)>);
+impl<'de, C, T> Visitor<'de> for SeqVis<C, T>
+where
+    C: FromIterator<T>,
+    T: FromHexBytes,
+{
+    type Value = C;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a sequence of hexadecimal strings")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut error = None;
+        let out = iter::from_fn(|| match seq.next_element::<Hex<T>>() {
+            Ok(v) => v.map(|v| v.0),
+            Err(e) => {
+                error = Some(e);
+                None
+            }
+        })
+        .collect();
+        match error {
+            Some(e) => Err(e),
+            None => Ok(out),
+        }
+    }
+}
+
+pub(crate) struct MapVis<M, K, V>(pub(crate) PhantomData<(M, K, V)>);
+impl<'de, M, K, V> Visitor<'de> for MapVis<M, K, V>
+where
+    M: FromIterator<(K, V)>,
+    K: FromHexBytes,
+    V: Deserialize<'de>,
+{
+    type Value = M;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a map with hexadecimal string keys")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut error = None;
+        let out = iter::from_fn(|| match map.next_entry::<Hex<K>, V>() {
+            Ok(v) => v.map(|(k, v)| (k.0, v)),
+            Err(e) => {
+                error = Some(e);
+                None
+            }
+        })
+        .collect();
+        match error {
+            Some(e) => Err(e),
+            None => Ok(out),
+        }
+    }
+}
+
+macro_rules! impl_collections {
+    ($module:ident, $Hex:ident, $case:literal) => {
+        pub(crate) mod $module {
+            /// Serialization and deserialization functions for an `Option`
+            #[doc = concat!("of bytes as ", $case, " hex string.")]
+            /// Can be used with `#[serde(with = "...")]`.
+            pub mod option {
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                use crate::{FromHexBytes, $Hex};
+
+                /// Serialize function for an optional hex string.
+                pub fn serialize<T, S>(value: &Option<T>, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                    T: AsRef<[u8]>,
+                {
+                    value.as_ref().map($Hex).serialize(serializer)
+                }
+
+                /// Deserialize function for an optional hex string. Can handle the
+                /// output of either `Hex` or `UpperHex`.
+                pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+                where
+                    D: Deserializer<'de>,
+                    T: FromHexBytes,
+                {
+                    Option::<crate::Hex<T>>::deserialize(deserializer).map(|v| v.map(|v| v.0))
+                }
+            }
+
+            /// Serialization and deserialization functions for a sequence
+            #[doc = concat!("of byte containers as ", $case, " hex strings.")]
+            /// Can be used with `#[serde(with = "...")]`.
+            pub mod seq {
+                use core::marker::PhantomData;
+
+                use serde::{Deserializer, Serializer};
+
+                use crate::{collections::SeqVis, FromHexBytes, $Hex};
+
+                /// Serialize function for a sequence of hex strings.
+                pub fn serialize<C, T, S>(value: &C, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                    for<'a> &'a C: IntoIterator<Item = &'a T>,
+                    T: AsRef<[u8]>,
+                {
+                    serializer.collect_seq(value.into_iter().map($Hex))
+                }
+
+                /// Deserialize function for a sequence of hex strings. Can handle
+                /// the output of either `Hex` or `UpperHex`.
+                pub fn deserialize<'de, C, T, D>(deserializer: D) -> Result<C, D::Error>
+                where
+                    D: Deserializer<'de>,
+                    C: FromIterator<T>,
+                    T: FromHexBytes,
+                {
+                    deserializer.deserialize_seq(SeqVis(PhantomData))
+                }
+            }
+
+            /// Serialization and deserialization functions for a map with
+            #[doc = concat!("byte container keys, which are represented as ", $case, " hex strings.")]
+            /// The values are handled by their own `Serialize` and `Deserialize`
+            /// implementations. Can be used with `#[serde(with = "...")]`.
+            pub mod map_keys {
+                use core::marker::PhantomData;
+
+                use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+                use crate::{collections::MapVis, FromHexBytes, $Hex};
+
+                /// Serialize function for a map with hex string keys.
+                pub fn serialize<M, K, V, S>(value: &M, serializer: S) -> Result<S::Ok, S::Error>
+                where
+                    S: Serializer,
+                    for<'a> &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+                    K: AsRef<[u8]>,
+                    V: Serialize,
+                {
+                    serializer.collect_map(value.into_iter().map(|(k, v)| ($Hex(k), v)))
+                }
+
+                /// Deserialize function for a map with hex string keys. Can handle
+                /// the output of either `Hex` or `UpperHex`.
+                pub fn deserialize<'de, M, K, V, D>(deserializer: D) -> Result<M, D::Error>
+                where
+                    D: Deserializer<'de>,
+                    M: FromIterator<(K, V)>,
+                    K: FromHexBytes,
+                    V: Deserialize<'de>,
+                {
+                    deserializer.deserialize_map(MapVis(PhantomData))
+                }
+            }
+        }
+    };
+}
+
+impl_collections!(lower, Hex, "lowercase");
+impl_collections!(upper, UpperHex, "uppercase");
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use serde_derive::{Deserialize, Serialize};
+
+    use crate::{
+        tests::{from_json, to_json},
+        Hex, UpperHex,
+    };
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Test {
+        #[serde(with = "crate::serde::option")]
+        a: Option<Vec<u8>>,
+        #[serde(with = "crate::serde_upper::option")]
+        b: Option<[u8; 2]>,
+        #[serde(with = "crate::serde::seq")]
+        c: Vec<[u8; 2]>,
+        #[serde(with = "crate::serde_upper::seq")]
+        d: Vec<Vec<u8>>,
+        #[serde(with = "crate::serde::map_keys")]
+        e: BTreeMap<[u8; 2], u32>,
+        #[serde(with = "crate::serde_upper::map_keys")]
+        f: HashMap<Vec<u8>, String>,
+    }
+
+    const JSON: &str = r#"{"a":"0199ff","b":null,"c":["0199","ff00"],"d":["ABCD",""],"e":{"0001":1,"00ff":2},"f":{"FF":"x"}}"#;
+
+    fn test_value() -> Test {
+        Test {
+            a: Some(vec![1, 0x99, 0xff]),
+            b: None,
+            c: vec![[1, 0x99], [0xff, 0]],
+            d: vec![vec![0xab, 0xcd], vec![]],
+            e: [([0, 1], 1), ([0, 0xff], 2)].into_iter().collect(),
+            f: [(vec![0xff], "x".to_owned())].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(serde_json::to_string(&test_value()).unwrap(), JSON);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let value: Test = serde_json::from_str(JSON).unwrap();
+        assert_eq!(value, test_value());
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let json = r#"{"a":"019","b":null,"c":[],"d":[],"e":{},"f":{}}"#;
+        assert!(serde_json::from_str::<Test>(json).is_err());
+
+        let json = r#"{"a":null,"b":null,"c":["01"],"d":[],"e":{},"f":{}}"#;
+        assert!(serde_json::from_str::<Test>(json).is_err());
+
+        let json = r#"{"a":null,"b":null,"c":[],"d":[],"e":{"zz":1},"f":{}}"#;
+        assert!(serde_json::from_str::<Test>(json).is_err());
+    }
+
+    #[test]
+    fn test_hex_map_key() {
+        let map: BTreeMap<Hex<[u8; 2]>, u32> = [(Hex([0, 1]), 1), (Hex([0xab, 0xcd]), 2)]
+            .into_iter()
+            .collect();
+
+        let json = to_json(&map);
+        assert_eq!(json, r#"{"data":{"0001":1,"abcd":2}}"#);
+        assert_eq!(from_json::<BTreeMap<Hex<[u8; 2]>, u32>>(&json), map);
+
+        let map: BTreeMap<UpperHex<Vec<u8>>, u32> = from_json(r#"{"data":{"ABCD":2}}"#);
+        assert_eq!(map[&UpperHex(vec![0xab, 0xcd])], 2);
+    }
+}
@@ -1,11 +1,16 @@
+use alloc::{string::String, vec, vec::Vec};
+
 use hex::FromHexError;
 
-use crate::encode::SMALL_SER_LEN;
+use crate::{encode::SMALL_SER_LEN, FromHexBytes};
 
 pub(crate) const SMALL_DES_LEN: usize = SMALL_SER_LEN / 2;
 
-fn fast_deserialize<V>(v: &str, out: impl FnOnce(&[u8]) -> V) -> Result<V, FromHexError> {
-    if v.len() % 2 != 0 {
+pub(crate) fn fast_deserialize<V>(
+    v: &str,
+    out: impl FnOnce(&[u8]) -> V,
+) -> Result<V, FromHexError> {
+    if !v.len().is_multiple_of(2) {
         return Err(FromHexError::OddLength);
     }
 
@@ -25,13 +30,48 @@ fn fast_deserialize<V>(v: &str, out: impl FnOnce(&[u8]) -> V) -> Result<V, FromH
     Ok(out(buf))
 }
 
+/// Decodes directly into the destination buffer of `T`, without any
+/// intermediate buffer.
 pub(crate) fn fast_deserialize_into<T>(v: &str) -> Result<T, FromHexError>
 where
-    T: for<'a> TryFrom<&'a [u8]>,
+    T: FromHexBytes,
 {
-    fast_deserialize(v, |buf| {
-        T::try_from(buf).map_err(|_| FromHexError::InvalidStringLength)
-    })?
+    if !v.len().is_multiple_of(2) {
+        return Err(FromHexError::OddLength);
+    }
+
+    let mut buf = T::buffer(v.len() / 2).ok_or(FromHexError::InvalidStringLength)?;
+    hex::decode_to_slice(v, buf.as_mut())?;
+    T::from_buffer(buf).ok_or(FromHexError::InvalidStringLength)
+}
+
+/// Decodes an owned hex string in its own allocation.
+///
+/// Each decoded byte is written to an index below the two digits it is
+/// read from, so the digits that are still needed are never overwritten.
+/// If the string is not valid hex, it is returned unchanged.
+pub(crate) fn decode_in_place(v: String) -> Result<Vec<u8>, String> {
+    if !v.len().is_multiple_of(2) || !v.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return Err(v);
+    }
+
+    let mut bytes = v.into_bytes();
+    let byte_len = bytes.len() / 2;
+    for i in 0..byte_len {
+        let high = hex_value(bytes[2 * i]);
+        let low = hex_value(bytes[2 * i + 1]);
+        bytes[i] = (high << 4) | low;
+    }
+    bytes.truncate(byte_len);
+    Ok(bytes)
+}
+
+fn hex_value(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        _ => c - b'A' + 10,
+    }
 }
 
 /// Decodes a hex string to a byte container.
@@ -39,7 +79,7 @@ where
 /// This accepts both lower and upper case strings.
 pub fn decode_into<T>(v: &str) -> Result<T, FromHexError>
 where
-    T: for<'a> TryFrom<&'a [u8]>,
+    T: FromHexBytes,
 {
     fast_deserialize_into(v)
 }
@@ -51,3 +91,20 @@ where
 pub fn decode<V>(v: &str, out: impl FnOnce(&[u8]) -> V) -> Result<V, FromHexError> {
     fast_deserialize(v, out)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_in_place() {
+        assert_eq!(
+            decode_in_place("0199fFaB".into()),
+            Ok(vec![1, 0x99, 0xff, 0xab])
+        );
+        assert_eq!(decode_in_place(String::new()), Ok(vec![]));
+        assert_eq!(decode_in_place("019".into()), Err("019".into()));
+        assert_eq!(decode_in_place("01xz".into()), Err("01xz".into()));
+        assert_eq!(decode_in_place("01\u{e9}".into()), Err("01\u{e9}".into()));
+    }
+}
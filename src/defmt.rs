@@ -0,0 +1,70 @@
+use ::defmt::{Format, Formatter};
+
+use crate::{Hex, UpperHex};
+
+macro_rules! impl_defmt {
+    ($Hex:ident, $hint:literal) => {
+        impl<T> Format for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn format(&self, f: Formatter<'_>) {
+                // NB: the bytes are sent as-is, and only encoded as hex by
+                // the host that decodes the log.
+                ::defmt::write!(f, $hint, self.0.as_ref())
+            }
+        }
+    };
+}
+
+impl_defmt!(Hex, "{=[u8]:x}");
+impl_defmt!(UpperHex, "{=[u8]:X}");
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    // NB: `defmt` only hands unencoded frames to the global logger, and
+    // leaves the encoding to the logger itself. The frames of `println!` are
+    // the interned format string, followed by the arguments.
+    static FRAMES: Mutex<Vec<u8>> = Mutex::new(Vec::new());
+
+    #[::defmt::global_logger]
+    struct TestLogger;
+
+    unsafe impl ::defmt::Logger for TestLogger {
+        fn acquire() {}
+        unsafe fn flush() {}
+        unsafe fn release() {}
+        unsafe fn write(bytes: &[u8]) {
+            FRAMES.lock().unwrap().extend_from_slice(bytes);
+        }
+    }
+
+    ::defmt::timestamp!("");
+
+    fn log<T: Format + ?Sized>(value: &T) -> Vec<u8> {
+        let mut frames = FRAMES.lock().unwrap();
+        frames.clear();
+        drop(frames);
+        ::defmt::println!("{}", value);
+        FRAMES.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn test_format() {
+        let frame = log(&Hex([1_u8, 0x99, 0xff]));
+        // the slice is sent with its length, without encoding it as hex
+        assert!(frame.windows(7).any(|w| w == [3, 0, 0, 0, 1, 0x99, 0xff]));
+
+        assert_eq!(log(&Hex(vec![1_u8, 0x99, 0xff])), frame);
+        assert_eq!(log(<&Hex<[u8]>>::from(&[1, 0x99, 0xff][..])), frame);
+
+        // only the interned format string differs for uppercase
+        let upper = log(&UpperHex([1_u8, 0x99, 0xff]));
+        assert_eq!(upper.len(), frame.len());
+        assert_ne!(upper, frame);
+    }
+}
@@ -1,16 +1,17 @@
-use std::{fmt, marker::PhantomData};
+use alloc::string::String;
+use core::{fmt, marker::PhantomData};
 
 use serde::{
     de::{Error, Unexpected, Visitor},
     Deserialize, Deserializer,
 };
 
-use crate::{decode::fast_deserialize_into, Hex, UpperHex};
+use crate::{decode::fast_deserialize_into, FromHexBytes, Hex, UpperHex};
 
 struct Vis<T>(PhantomData<T>);
 impl<'a, T> Visitor<'a> for Vis<T>
 where
-    T: for<'b> TryFrom<&'b [u8]>,
+    T: FromHexBytes,
 {
     type Value = T;
 
@@ -26,11 +27,27 @@ where
             .map_err(|_| Error::invalid_type(Unexpected::Str(v), &self))?;
         Ok(value)
     }
+
+    fn visit_borrowed_str<E>(self, v: &'a str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        // NB: this already decodes from the borrowed input straight into the
+        // destination buffer of `T`, without an intermediate copy.
+        self.visit_str(v)
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        T::from_hex_string(v).or_else(|v| self.visit_str(&v))
+    }
 }
 
 impl<'a, T> Deserialize<'a> for Hex<T>
 where
-    T: for<'b> TryFrom<&'b [u8]>,
+    T: FromHexBytes,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -42,7 +59,7 @@ where
 
 impl<'a, T> Deserialize<'a> for UpperHex<T>
 where
-    T: for<'b> TryFrom<&'b [u8]>,
+    T: FromHexBytes,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -57,7 +74,7 @@ where
 pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
 where
     D: Deserializer<'de>,
-    T: for<'a> TryFrom<&'a [u8]>,
+    T: FromHexBytes,
 {
     Hex::<T>::deserialize(deserializer).map(|v| v.0)
 }
@@ -113,4 +130,57 @@ mod tests {
         let hex: UpperHex<_> = from_json(r#"{"data":"0199fF"}"#);
         assert_eq!(hex, UpperHex([1_u8, 0x99, 0xff]));
     }
+
+    #[test]
+    fn test_owned_and_borrowed() {
+        use serde::de::{
+            value::{BorrowedStrDeserializer, Error as ValueError},
+            IntoDeserializer,
+        };
+
+        let s = String::from("0199fF");
+        let ptr = s.as_ptr();
+        let hex = Hex::<Vec<u8>>::deserialize(s.into_deserializer());
+        let hex: Hex<Vec<u8>> = hex.map_err(|err: ValueError| err).unwrap();
+        assert_eq!(hex, Hex(vec![1, 0x99, 0xff]));
+        // decoded in the allocation of the string
+        assert_eq!(hex.0.as_ptr(), ptr);
+
+        let hex: Result<UpperHex<Box<[u8]>>, ValueError> =
+            UpperHex::deserialize(String::from("abcd").into_deserializer());
+        assert_eq!(hex.unwrap(), UpperHex(vec![0xab, 0xcd].into()));
+
+        let hex: Result<Hex<[u8; 3]>, ValueError> =
+            Hex::deserialize(String::from("0199ff").into_deserializer());
+        assert_eq!(hex.unwrap(), Hex([1, 0x99, 0xff]));
+
+        let hex: Result<Hex<Vec<u8>>, ValueError> =
+            Hex::deserialize(BorrowedStrDeserializer::new("0199ff"));
+        assert_eq!(hex.unwrap(), Hex(vec![1, 0x99, 0xff]));
+
+        for s in ["0199f", "0199fg", "0199ff00"] {
+            let owned: Result<Hex<[u8; 3]>, ValueError> =
+                Hex::deserialize(String::from(s).into_deserializer());
+            let borrowed: Result<Hex<[u8; 3]>, ValueError> =
+                Hex::deserialize(BorrowedStrDeserializer::new(s));
+            assert_eq!(
+                owned.unwrap_err().to_string(),
+                borrowed.unwrap_err().to_string()
+            );
+        }
+        let err: Result<Hex<Vec<u8>>, ValueError> =
+            Hex::deserialize(String::from("01x9").into_deserializer());
+        assert_eq!(
+            err.unwrap_err().to_string(),
+            r#"invalid type: string "01x9", expected a hexadecimal string"#
+        );
+    }
+
+    #[test]
+    fn test_derive() {
+        let test: Test = serde_json::from_str(r#"{"_a":"01","_b":"ab","_c":"99FF"}"#).unwrap();
+        assert_eq!(test._a, Hex(vec![1]));
+        assert_eq!(test._b, UpperHex(vec![0xab]));
+        assert_eq!(test._c, [0x99, 0xff]);
+    }
 }
@@ -0,0 +1,278 @@
+#[cfg(any(feature = "diesel-postgres", feature = "diesel-mysql"))]
+use std::io::Write;
+
+use diesel::{
+    backend::Backend,
+    deserialize::{self, FromSql, Queryable},
+    dsl::AsExprOf,
+    expression::AsExpression,
+    serialize::{self, Output, ToSql},
+    sql_types::{Binary, Nullable, SingleValue, Text},
+};
+
+use crate::{decode_into, from_hex_bytes::from_slice, FromHexBytes, Hex, UpperHex};
+
+fn length_error<T: FromHexBytes>(len: usize) -> Box<dyn std::error::Error + Send + Sync> {
+    match T::expected_len() {
+        Some(n) => format!("expected {n} bytes, found {len} bytes").into(),
+        None => format!("unsupported length of {len} bytes").into(),
+    }
+}
+
+// NB: the wrappers are bound as the `std` types they are stored as, which
+// only needs the public `AsExpression` impls of `diesel`. Its derive does
+// not support the `?Sized` parameter of the wrappers.
+macro_rules! impl_as_expression {
+    (
+        $Hex:ident,
+        $SqlType:ty,
+        $Value:ty = |$hex:ident| $value:expr,
+        $RefValue:ty = |$ref_hex:ident| $ref_value:expr
+    ) => {
+        impl<T> AsExpression<$SqlType> for $Hex<T>
+        where
+            T: AsRef<[u8]>,
+        {
+            type Expression = AsExprOf<$Value, $SqlType>;
+
+            fn as_expression(self) -> Self::Expression {
+                let $hex = self;
+                AsExpression::<$SqlType>::as_expression($value)
+            }
+        }
+
+        impl<'a, T> AsExpression<$SqlType> for &'a $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            type Expression = AsExprOf<$RefValue, $SqlType>;
+
+            fn as_expression(self) -> Self::Expression {
+                let $ref_hex = self;
+                AsExpression::<$SqlType>::as_expression($ref_value)
+            }
+        }
+    };
+}
+
+macro_rules! impl_diesel {
+    ($Hex:ident) => {
+        impl_as_expression!(
+            $Hex,
+            Binary,
+            Vec<u8> = |hex| hex.0.as_ref().to_vec(),
+            &'a [u8] = |hex| hex.0.as_ref()
+        );
+        impl_as_expression!(
+            $Hex,
+            Nullable<Binary>,
+            Vec<u8> = |hex| hex.0.as_ref().to_vec(),
+            &'a [u8] = |hex| hex.0.as_ref()
+        );
+        impl_as_expression!(
+            $Hex,
+            Text,
+            String = |hex| hex.to_string(),
+            String = |hex| hex.to_string()
+        );
+        impl_as_expression!(
+            $Hex,
+            Nullable<Text>,
+            String = |hex| hex.to_string(),
+            String = |hex| hex.to_string()
+        );
+
+        impl<T, DB> ToSql<Binary, DB> for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+            DB: Backend,
+            [u8]: ToSql<Binary, DB>,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, DB>) -> serialize::Result {
+                self.0.as_ref().to_sql(out)
+            }
+        }
+
+        impl<T, DB> FromSql<Binary, DB> for $Hex<T>
+        where
+            T: FromHexBytes,
+            DB: Backend,
+            Vec<u8>: FromSql<Binary, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                let bytes = Vec::<u8>::from_sql(bytes)?;
+                from_slice(&bytes)
+                    .map($Hex)
+                    .ok_or_else(|| length_error::<T>(bytes.len()))
+            }
+        }
+
+        impl<T, DB> FromSql<Text, DB> for $Hex<T>
+        where
+            T: FromHexBytes,
+            DB: Backend,
+            String: FromSql<Text, DB>,
+        {
+            fn from_sql(bytes: DB::RawValue<'_>) -> deserialize::Result<Self> {
+                let s = String::from_sql(bytes)?;
+                Ok($Hex(decode_into(&s)?))
+            }
+        }
+
+        impl<T, ST, DB> Queryable<ST, DB> for $Hex<T>
+        where
+            DB: Backend,
+            ST: SingleValue,
+            Self: FromSql<ST, DB>,
+        {
+            type Row = Self;
+
+            fn build(row: Self) -> deserialize::Result<Self> {
+                Ok(row)
+            }
+        }
+
+        #[cfg(feature = "diesel-sqlite")]
+        impl<T> ToSql<Text, diesel::sqlite::Sqlite> for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut Output<'b, '_, diesel::sqlite::Sqlite>,
+            ) -> serialize::Result {
+                out.set_value(self.to_string());
+                Ok(serialize::IsNull::No)
+            }
+        }
+
+        #[cfg(feature = "diesel-postgres")]
+        impl<T> ToSql<Text, diesel::pg::Pg> for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn to_sql<'b>(&'b self, out: &mut Output<'b, '_, diesel::pg::Pg>) -> serialize::Result {
+                write!(out, "{self}")?;
+                Ok(serialize::IsNull::No)
+            }
+        }
+
+        #[cfg(feature = "diesel-mysql")]
+        impl<T> ToSql<Text, diesel::mysql::Mysql> for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn to_sql<'b>(
+                &'b self,
+                out: &mut Output<'b, '_, diesel::mysql::Mysql>,
+            ) -> serialize::Result {
+                write!(out, "{self}")?;
+                Ok(serialize::IsNull::No)
+            }
+        }
+    };
+}
+
+impl_diesel!(Hex);
+impl_diesel!(UpperHex);
+
+#[cfg(all(test, feature = "diesel-sqlite"))]
+mod tests {
+    use diesel::{
+        dsl::sql, result::Error, Connection, ExpressionMethods, QueryDsl, RunQueryDsl,
+        SqliteConnection,
+    };
+
+    use super::*;
+
+    diesel::table! {
+        keys (id) {
+            id -> Integer,
+            raw -> Binary,
+            text -> Text,
+            note -> Nullable<Binary>,
+        }
+    }
+
+    fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::establish(":memory:").unwrap();
+        diesel::sql_query(
+            "CREATE TABLE keys (id INTEGER PRIMARY KEY, raw BLOB NOT NULL, text TEXT NOT NULL, note BLOB)",
+        )
+        .execute(&mut conn)
+        .unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut conn = connect();
+        let key = Hex([1_u8, 0x99, 0xff, 0]);
+        diesel::insert_into(keys::table)
+            .values((
+                keys::raw.eq(key),
+                keys::text.eq(UpperHex(vec![0xab_u8, 0xcd])),
+                keys::note.eq(None::<Hex<Vec<u8>>>),
+            ))
+            .execute(&mut conn)
+            .unwrap();
+
+        let row = keys::table
+            .filter(keys::raw.eq(&key))
+            .select((keys::raw, keys::text, keys::note))
+            .first::<(Hex<[u8; 4]>, Hex<Vec<u8>>, Option<Hex<Vec<u8>>>)>(&mut conn)
+            .unwrap();
+        assert_eq!(row, (key, Hex(vec![0xab, 0xcd]), None));
+
+        let stored = keys::table
+            .select((sql::<Text>("hex(raw)"), keys::text))
+            .first::<(String, String)>(&mut conn)
+            .unwrap();
+        assert_eq!(stored, ("0199FF00".to_owned(), "ABCD".to_owned()));
+    }
+
+    #[test]
+    fn test_bind_by_reference() {
+        let mut conn = connect();
+        let text = UpperHex(vec![0xab_u8, 0xcd]);
+        let note = Some(Hex([0xef_u8]));
+        diesel::insert_into(keys::table)
+            .values((
+                keys::raw.eq(Hex(&[1_u8, 0x99][..])),
+                keys::text.eq(&text),
+                keys::note.eq(note),
+            ))
+            .execute(&mut conn)
+            .unwrap();
+
+        let row = keys::table
+            .filter(keys::text.eq(&text))
+            .filter(keys::note.eq(Hex([0xef_u8])))
+            .select((keys::raw, keys::note))
+            .first::<(Hex<Vec<u8>>, Option<Hex<[u8; 1]>>)>(&mut conn)
+            .unwrap();
+        assert_eq!(row, (Hex(vec![1, 0x99]), note));
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut conn = connect();
+        diesel::sql_query("INSERT INTO keys (raw, text) VALUES (x'0199', 'zz')")
+            .execute(&mut conn)
+            .unwrap();
+
+        let err = keys::table
+            .select(keys::raw)
+            .first::<Hex<[u8; 4]>>(&mut conn)
+            .unwrap_err();
+        assert!(matches!(err, Error::DeserializationError(_)));
+        assert!(format!("{err:?}").contains("expected 4 bytes, found 2 bytes"));
+
+        let err = keys::table
+            .select(keys::text)
+            .first::<Hex<Vec<u8>>>(&mut conn)
+            .unwrap_err();
+        assert!(matches!(err, Error::DeserializationError(_)));
+        assert!(format!("{err:?}").contains("InvalidHexCharacter { c: 'z', index: 0 }"));
+    }
+}
@@ -0,0 +1,282 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    dump::{dump_char, DUMP_LINE_LEN},
+    encode::{byte2hex, HEX_CHARS_LOWER},
+};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// Diff of two byte buffers in `xxd` hex dump layout.
+///
+/// Only the dump lines that differ are shown, first for the left and then
+/// for the right buffer. Differing bytes are marked with `^` in a line below,
+/// or highlighted with ANSI colors if enabled.
+///
+/// ```rust
+/// use easy_hex::HexDiff;
+///
+/// let diff = HexDiff::new(b"hello world", b"hello World!");
+/// assert_eq!(
+///     diff.to_string(),
+///     "\
+/// --- left: 11 bytes
+/// +++ right: 12 bytes
+/// -00000000: 6865 6c6c 6f20 776f 726c 64              hello world
+/// +00000000: 6865 6c6c 6f20 576f 726c 6421            hello World!
+///                           ^^          ^^                  ^    ^
+/// "
+/// );
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct HexDiff<'a> {
+    left: &'a [u8],
+    right: &'a [u8],
+    color: bool,
+}
+
+impl<'a> HexDiff<'a> {
+    /// Creates a diff between `left` and `right`.
+    pub fn new(left: &'a [u8], right: &'a [u8]) -> Self {
+        Self {
+            left,
+            right,
+            color: false,
+        }
+    }
+
+    /// Sets whether to highlight differing bytes with ANSI colors instead
+    /// of marking them in a separate line.
+    pub fn color(mut self, color: bool) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+/// Writes one side of a differing dump line.
+fn fmt_side(
+    f: &mut fmt::Formatter<'_>,
+    color: Option<&str>,
+    sign: char,
+    offset: usize,
+    line: &[u8],
+    diff: &[bool],
+) -> fmt::Result {
+    let highlight = |differs: bool| match color {
+        Some(color) if differs => (color, RESET),
+        _ => ("", ""),
+    };
+
+    write!(f, "{sign}{offset:08x}: ")?;
+    for (i, &differs) in diff.iter().enumerate() {
+        if i > 0 && i % 2 == 0 {
+            f.write_str(" ")?;
+        }
+        match line.get(i) {
+            Some(&byte) => {
+                let (high, low) = byte2hex(byte, HEX_CHARS_LOWER);
+                let (start, end) = highlight(differs);
+                write!(f, "{start}{}{}{end}", char::from(high), char::from(low))?;
+            }
+            None => f.write_str("  ")?,
+        }
+    }
+    f.write_str("  ")?;
+    for (&byte, &differs) in line.iter().zip(diff) {
+        let (start, end) = highlight(differs);
+        write!(f, "{start}{}{end}", char::from(dump_char(byte)))?;
+    }
+    writeln!(f)
+}
+
+/// Writes the line marking the differing bytes of a dump line.
+fn fmt_markers(f: &mut fmt::Formatter<'_>, diff: &[bool]) -> fmt::Result {
+    let mut out = String::from("           ");
+    for (i, &differs) in diff.iter().enumerate() {
+        if i > 0 && i % 2 == 0 {
+            out.push(' ');
+        }
+        out.push_str(if differs { "^^" } else { "  " });
+    }
+    out.push_str("  ");
+    out.extend(diff.iter().map(|&differs| if differs { '^' } else { ' ' }));
+    writeln!(f, "{}", out.trim_end())
+}
+
+/// Number of bytes, with the unit in singular or plural.
+struct ByteCount(usize);
+
+impl fmt::Display for ByteCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            1 => write!(f, "1 byte"),
+            n => write!(f, "{n} bytes"),
+        }
+    }
+}
+
+impl fmt::Display for HexDiff<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "--- left: {}", ByteCount(self.left.len()))?;
+        writeln!(f, "+++ right: {}", ByteCount(self.right.len()))?;
+
+        let mut left = self.left.chunks(DUMP_LINE_LEN);
+        let mut right = self.right.chunks(DUMP_LINE_LEN);
+        let mut offset = 0;
+        loop {
+            let (l, r) = match (left.next(), right.next()) {
+                (None, None) => break,
+                (l, r) => (l.unwrap_or_default(), r.unwrap_or_default()),
+            };
+
+            if l != r {
+                let diff: Vec<bool> = (0..DUMP_LINE_LEN).map(|i| l.get(i) != r.get(i)).collect();
+                let (red, green) = if self.color {
+                    (Some(RED), Some(GREEN))
+                } else {
+                    (None, None)
+                };
+                if !l.is_empty() {
+                    fmt_side(f, red, '-', offset, l, &diff)?;
+                }
+                if !r.is_empty() {
+                    fmt_side(f, green, '+', offset, r, &diff)?;
+                }
+                if !self.color {
+                    fmt_markers(f, &diff)?;
+                }
+            }
+            offset += DUMP_LINE_LEN;
+        }
+        Ok(())
+    }
+}
+
+/// Asserts that two byte containers are equal, like [`assert_eq!`].
+///
+/// Both sides can be anything that implements `AsRef<[u8]>`, like `Hex<T>`.
+/// On failure, the panic message shows a [`HexDiff`] of both sides.
+///
+/// ```rust,should_panic
+/// use easy_hex::{assert_hex_eq, Hex};
+///
+/// assert_hex_eq!(Hex(vec![1, 2, 3]), [1, 2, 4]);
+/// ```
+#[macro_export]
+macro_rules! assert_hex_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &[u8] = ::core::convert::AsRef::<[u8]>::as_ref(left);
+                let right: &[u8] = ::core::convert::AsRef::<[u8]>::as_ref(right);
+                if left != right {
+                    ::core::panic!(
+                        "assertion `left == right` failed\n{}",
+                        $crate::HexDiff::new(left, right)
+                    );
+                }
+            }
+        }
+    };
+    ($left:expr, $right:expr, $($arg:tt)+) => {
+        match (&$left, &$right) {
+            (left, right) => {
+                let left: &[u8] = ::core::convert::AsRef::<[u8]>::as_ref(left);
+                let right: &[u8] = ::core::convert::AsRef::<[u8]>::as_ref(right);
+                if left != right {
+                    ::core::panic!(
+                        "assertion `left == right` failed: {}\n{}",
+                        ::core::format_args!($($arg)+),
+                        $crate::HexDiff::new(left, right)
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Hex;
+
+    #[test]
+    fn test_diff() {
+        let left: Vec<u8> = (0..40).collect();
+        let mut right = left.clone();
+        right[17] = 0xff;
+        right.truncate(35);
+        assert_eq!(
+            HexDiff::new(&left, &right).to_string(),
+            "\
+--- left: 40 bytes
++++ right: 35 bytes
+-00000010: 1011 1213 1415 1617 1819 1a1b 1c1d 1e1f  ................
++00000010: 10ff 1213 1415 1617 1819 1a1b 1c1d 1e1f  ................
+             ^^                                      ^
+-00000020: 2021 2223 2425 2627                       !\"#$%&'
++00000020: 2021 22                                   !\"
+                  ^^ ^^^^ ^^^^                         ^^^^^
+"
+        );
+    }
+
+    #[test]
+    fn test_diff_equal() {
+        assert_eq!(
+            HexDiff::new(b"abc", b"abc").to_string(),
+            "--- left: 3 bytes\n+++ right: 3 bytes\n"
+        );
+        assert_eq!(
+            HexDiff::new(b"a", b"a").to_string(),
+            "--- left: 1 byte\n+++ right: 1 byte\n"
+        );
+        assert_eq!(
+            HexDiff::new(b"", b"a").to_string(),
+            "\
+--- left: 0 bytes
++++ right: 1 byte
++00000000: 61                                       a
+           ^^                                       ^
+"
+        );
+    }
+
+    #[test]
+    fn test_diff_color() {
+        assert_eq!(
+            HexDiff::new(b"ab", b"ac").color(true).to_string(),
+            "\
+--- left: 2 bytes
++++ right: 2 bytes
+-00000000: 61\x1b[31m62\x1b[0m                                     a\x1b[31mb\x1b[0m
++00000000: 61\x1b[32m63\x1b[0m                                     a\x1b[32mc\x1b[0m
+"
+        );
+    }
+
+    #[test]
+    fn test_assert() {
+        assert_hex_eq!(Hex(vec![1, 2]), [1, 2]);
+        assert_hex_eq!(b"ab", Hex(&b"ab"[..]), "with message {}", 1);
+
+        let err = std::panic::catch_unwind(|| assert_hex_eq!(Hex([1]), [2], "data {}", 1))
+            .unwrap_err()
+            .downcast::<String>()
+            .unwrap();
+        assert_eq!(
+            *err,
+            "\
+assertion `left == right` failed: data 1
+--- left: 1 byte
++++ right: 1 byte
+-00000000: 01                                       .
++00000000: 02                                       .
+           ^^                                       ^
+"
+        );
+    }
+}
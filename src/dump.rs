@@ -0,0 +1,182 @@
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+#[cfg(feature = "std")]
+use crate::encode::{byte2hex, HEX_CHARS_LOWER, HEX_CHARS_UPPER};
+
+/// Number of bytes per line of a dump.
+pub(crate) const DUMP_LINE_LEN: usize = 16;
+
+/// Width of the hex column of a dump line: 8 groups of 2 bytes,
+/// separated by spaces.
+#[cfg(feature = "std")]
+pub(crate) const DUMP_HEX_WIDTH: usize = DUMP_LINE_LEN * 2 + DUMP_LINE_LEN / 2 - 1;
+
+/// Returns the character shown for `byte` in the ASCII column of a dump.
+pub(crate) fn dump_char(byte: u8) -> u8 {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        byte
+    } else {
+        b'.'
+    }
+}
+
+/// Appends a single `xxd` style dump line to `out`, without a newline.
+#[cfg(feature = "std")]
+pub(crate) fn dump_line(out: &mut Vec<u8>, offset: u64, line: &[u8], alpha: &[u8; 16]) {
+    debug_assert!(line.len() <= DUMP_LINE_LEN);
+
+    // NB: Writing to a vector can never fail
+    let _ = write!(out, "{offset:08x}: ");
+    let hex_start = out.len();
+    for (i, &byte) in line.iter().enumerate() {
+        if i > 0 && i % 2 == 0 {
+            out.push(b' ');
+        }
+        let (high, low) = byte2hex(byte, alpha);
+        out.extend_from_slice(&[high, low]);
+    }
+    let padding = DUMP_HEX_WIDTH - (out.len() - hex_start);
+    out.extend(std::iter::repeat_n(b' ', padding + 2));
+    out.extend(line.iter().copied().map(dump_char));
+}
+
+/// Streaming `xxd` style hex dump.
+///
+/// All bytes written to this adapter get formatted as hex dump lines of 16
+/// bytes each, with an offset column and an ASCII column, and written to the
+/// inner writer. A partial last line is written by [`DumpWriter::finish`].
+///
+/// ```rust
+/// use std::io::Write;
+/// use easy_hex::DumpWriter;
+///
+/// let mut dump = DumpWriter::new(Vec::new());
+/// dump.write_all(b"hello world\n").unwrap();
+/// let out = dump.finish().unwrap();
+/// assert_eq!(
+///     String::from_utf8(out).unwrap(),
+///     "00000000: 6865 6c6c 6f20 776f 726c 640a            hello world.\n"
+/// );
+/// ```
+#[cfg(feature = "std")]
+pub struct DumpWriter<W: Write> {
+    inner: W,
+    alpha: &'static [u8; 16],
+    offset: u64,
+    line: Vec<u8>,
+    buf: Vec<u8>,
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> DumpWriter<W> {
+    /// Creates a new dump that writes to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            alpha: HEX_CHARS_LOWER,
+            offset: 0,
+            line: Vec::with_capacity(DUMP_LINE_LEN),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Sets whether to write uppercase hex.
+    pub fn upper(mut self, upper: bool) -> Self {
+        self.alpha = if upper {
+            HEX_CHARS_UPPER
+        } else {
+            HEX_CHARS_LOWER
+        };
+        self
+    }
+
+    /// Sets the offset shown for the first byte.
+    pub fn offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Writes the partial last line, flushes the dump, and returns the
+    /// inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if !self.line.is_empty() {
+            self.buf.clear();
+            dump_line(&mut self.buf, self.offset, &self.line, self.alpha);
+            self.buf.push(b'\n');
+            self.inner.write_all(&self.buf)?;
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: Write> Write for DumpWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.clear();
+        let mut rest = data;
+        while !rest.is_empty() {
+            let n = rest.len().min(DUMP_LINE_LEN - self.line.len());
+            self.line.extend_from_slice(&rest[..n]);
+            rest = &rest[n..];
+
+            if self.line.len() == DUMP_LINE_LEN {
+                dump_line(&mut self.buf, self.offset, &self.line, self.alpha);
+                self.buf.push(b'\n');
+                self.offset += DUMP_LINE_LEN as u64;
+                self.line.clear();
+            }
+        }
+        self.inner.write_all(&self.buf)?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    fn dump(data: &[u8], dump: DumpWriter<Vec<u8>>) -> String {
+        let mut dump = dump;
+        for chunk in data.chunks(5) {
+            dump.write_all(chunk).unwrap();
+        }
+        String::from_utf8(dump.finish().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_dump() {
+        let data: Vec<u8> = (0x00..0x24).collect();
+        assert_eq!(
+            dump(&data, DumpWriter::new(Vec::new())),
+            "\
+00000000: 0001 0203 0405 0607 0809 0a0b 0c0d 0e0f  ................
+00000010: 1011 1213 1415 1617 1819 1a1b 1c1d 1e1f  ................
+00000020: 2021 2223                                 !\"#
+"
+        );
+    }
+
+    #[test]
+    fn test_dump_options() {
+        let data = b"Hello, World!\xff\x7f~";
+        assert_eq!(
+            dump(data, DumpWriter::new(Vec::new()).upper(true).offset(0x100)),
+            "00000100: 4865 6C6C 6F2C 2057 6F72 6C64 21FF 7F7E  Hello, World!..~\n"
+        );
+    }
+
+    #[test]
+    fn test_dump_empty() {
+        assert_eq!(dump(&[], DumpWriter::new(Vec::new())), "");
+        assert_eq!(
+            dump(&[0xab], DumpWriter::new(Vec::new())),
+            "00000000: ab                                       .\n"
+        );
+    }
+}
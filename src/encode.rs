@@ -1,3 +1,5 @@
+use alloc::vec;
+
 use hex::FromHexError;
 
 use crate::{LOWER, UPPER};
@@ -47,14 +49,34 @@ where
     let _ = encode_to_slice(v, buf, alpha);
 
     // SAFTEY: buffer will only contain ASCII bytes
-    let s: &str = unsafe { std::str::from_utf8_unchecked(buf) };
+    let s: &str = unsafe { core::str::from_utf8_unchecked(buf) };
     out(s)
 }
 
+/// Encodes in pieces through a fixed stack buffer, for formatters that
+/// have to work without allocating.
+#[cfg(feature = "ufmt")]
+pub(crate) fn chunked_serialize<E, const U: bool>(
+    v: &[u8],
+    mut out: impl FnMut(&str) -> Result<(), E>,
+) -> Result<(), E> {
+    let alpha = if U { HEX_CHARS_UPPER } else { HEX_CHARS_LOWER };
+    let mut array = [0; SMALL_SER_LEN];
+    for chunk in v.chunks(SMALL_SER_LEN / 2) {
+        let buf = &mut array[..chunk.len() * 2];
+        // NB: This can never fail, as we ensure the buffer has the right size
+        let _ = encode_to_slice(chunk, buf, alpha);
+
+        // SAFETY: buffer will only contain ASCII bytes
+        out(unsafe { core::str::from_utf8_unchecked(buf) })?;
+    }
+    Ok(())
+}
+
 // --- code taken from hex crate ----------------
 
-const HEX_CHARS_LOWER: &[u8; 16] = b"0123456789abcdef";
-const HEX_CHARS_UPPER: &[u8; 16] = b"0123456789ABCDEF";
+pub(crate) const HEX_CHARS_LOWER: &[u8; 16] = b"0123456789abcdef";
+pub(crate) const HEX_CHARS_UPPER: &[u8; 16] = b"0123456789ABCDEF";
 
 /// taken from hex crate
 fn encode_to_slice<T: AsRef<[u8]>>(
@@ -95,7 +117,7 @@ fn generate_iter(len: usize) -> impl Iterator<Item = (usize, usize)> {
 /// the inverse of `val`.
 #[inline]
 #[must_use]
-fn byte2hex(byte: u8, table: &[u8; 16]) -> (u8, u8) {
+pub(crate) fn byte2hex(byte: u8, table: &[u8; 16]) -> (u8, u8) {
     let high = table[((byte & 0xf0) >> 4) as usize];
     let low = table[(byte & 0x0f) as usize];
 
@@ -1,36 +1,170 @@
-use std::fmt;
+use core::fmt;
 
 use crate::{encode::fast_serialize, Hex, UpperHex, LOWER, UPPER};
 
+/// Number of bytes per row in the `{:#?}` debug view.
+const DEBUG_ROW_LEN: usize = 16;
+
+/// Formats `bytes` as a hex string, respecting the formatter options.
+///
+/// - Precision limits the number of _bytes_ that get formatted.
+/// - Width, fill and alignment behave like for strings.
+/// - The `0` flag pads with zeros on the left, like for integers.
+pub(crate) fn fmt_hex<const U: bool>(bytes: &[u8], f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let bytes = match f.precision() {
+        Some(precision) => &bytes[..precision.min(bytes.len())],
+        None => bytes,
+    };
+    fast_serialize::<_, _, U>(bytes, |s| pad(s, f))
+}
+
+fn pad(s: &str, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    // NB: `s` only contains ASCII, so its length equals its char count
+    let padding = f.width().unwrap_or(0).saturating_sub(s.len());
+    if padding == 0 {
+        return f.write_str(s);
+    }
+
+    if f.sign_aware_zero_pad() {
+        write_fill(f, '0', padding)?;
+        return f.write_str(s);
+    }
+
+    let (pre, post) = match f.align() {
+        None | Some(fmt::Alignment::Left) => (0, padding),
+        Some(fmt::Alignment::Right) => (padding, 0),
+        Some(fmt::Alignment::Center) => (padding / 2, padding - padding / 2),
+    };
+    let fill = f.fill();
+    write_fill(f, fill, pre)?;
+    f.write_str(s)?;
+    write_fill(f, fill, post)
+}
+
+fn write_fill(f: &mut fmt::Formatter<'_>, fill: char, n: usize) -> fmt::Result {
+    for _ in 0..n {
+        fmt::Write::write_char(f, fill)?;
+    }
+    Ok(())
+}
+
+/// Structured `{:#?}` view of a byte sequence.
+pub(crate) fn fmt_debug_struct<const U: bool>(
+    name: &str,
+    bytes: &[u8],
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let len = bytes.len();
+    let bytes = match f.precision() {
+        Some(precision) => &bytes[..precision.min(len)],
+        None => bytes,
+    };
+    f.debug_struct(name)
+        .field("len", &len)
+        .field("data", &Rows::<U>(bytes))
+        .finish()
+}
+
+struct Rows<'a, const U: bool>(&'a [u8]);
+impl<const U: bool> fmt::Debug for Rows<'_, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.0.chunks(DEBUG_ROW_LEN).map(Row::<U>))
+            .finish()
+    }
+}
+
+struct Row<'a, const U: bool>(&'a [u8]);
+impl<const U: bool> fmt::Debug for Row<'_, U> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fast_serialize::<_, _, U>(self.0, |s| f.write_str(s))
+    }
+}
+
 macro_rules! impl_fmt {
-    ($Hex:ident, $Trait:ident, $case:ident, $FmtTrait:ident) => {
+    ($Hex:ident, $Trait:ident, $case:ident) => {
         impl<T> fmt::$Trait for $Hex<T>
         where
             T: AsRef<[u8]> + ?Sized,
         {
             fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-                fast_serialize::<_, _, $case>(&self.0, |s| fmt::$FmtTrait::fmt(s, f))
+                fmt_hex::<$case>(self.0.as_ref(), f)
             }
         }
     };
 }
 
-impl_fmt!(Hex, Display, LOWER, Display);
-impl_fmt!(Hex, Debug, LOWER, Debug);
-impl_fmt!(Hex, LowerHex, LOWER, Display);
-impl_fmt!(Hex, UpperHex, UPPER, Display);
+macro_rules! impl_debug {
+    ($Hex:ident, $case:ident) => {
+        impl<T> fmt::Debug for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                if f.alternate() {
+                    fmt_debug_struct::<$case>(stringify!($Hex), self.0.as_ref(), f)
+                } else {
+                    fmt_hex::<$case>(self.0.as_ref(), f)
+                }
+            }
+        }
+    };
+}
+
+impl_fmt!(Hex, Display, LOWER);
+impl_debug!(Hex, LOWER);
+impl_fmt!(Hex, LowerHex, LOWER);
+impl_fmt!(Hex, UpperHex, UPPER);
+
+impl_fmt!(UpperHex, Display, UPPER);
+impl_debug!(UpperHex, UPPER);
+impl_fmt!(UpperHex, LowerHex, LOWER);
+impl_fmt!(UpperHex, UpperHex, UPPER);
 
-impl_fmt!(UpperHex, Display, UPPER, Display);
-impl_fmt!(UpperHex, Debug, UPPER, Debug);
-impl_fmt!(UpperHex, LowerHex, LOWER, Display);
-impl_fmt!(UpperHex, UpperHex, UPPER, Display);
+#[cfg(feature = "serde")]
+mod lenient {
+    use super::*;
+    use crate::LenientHex;
+
+    impl_fmt!(LenientHex, Display, LOWER);
+    impl_debug!(LenientHex, LOWER);
+    impl_fmt!(LenientHex, LowerHex, LOWER);
+    impl_fmt!(LenientHex, UpperHex, UPPER);
+}
+
+#[cfg(feature = "subtle")]
+mod ct {
+    use super::*;
+    use crate::CtHex;
+
+    impl_fmt!(CtHex, Display, LOWER);
+    impl_debug!(CtHex, LOWER);
+    impl_fmt!(CtHex, LowerHex, LOWER);
+    impl_fmt!(CtHex, UpperHex, UPPER);
+}
+
+#[cfg(feature = "rkyv")]
+mod archived {
+    use super::*;
+    use crate::{ArchivedHex, ArchivedUpperHex};
+
+    impl_fmt!(ArchivedHex, Display, LOWER);
+    impl_debug!(ArchivedHex, LOWER);
+    impl_fmt!(ArchivedHex, LowerHex, LOWER);
+    impl_fmt!(ArchivedHex, UpperHex, UPPER);
+
+    impl_fmt!(ArchivedUpperHex, Display, UPPER);
+    impl_debug!(ArchivedUpperHex, UPPER);
+    impl_fmt!(ArchivedUpperHex, LowerHex, LOWER);
+    impl_fmt!(ArchivedUpperHex, UpperHex, UPPER);
+}
 
 #[test]
 fn test_lower() {
     let hex = Hex([1_u8, 0x99, 0xff]);
 
     assert_eq!(format!("{}", hex), "0199ff");
-    assert_eq!(format!("{:?}", hex), "\"0199ff\"");
+    assert_eq!(format!("{:?}", hex), "0199ff");
     assert_eq!(format!("{:x}", hex), "0199ff");
     assert_eq!(format!("{:X}", hex), "0199FF");
 }
@@ -40,7 +174,118 @@ fn test_upper() {
     let hex = UpperHex([1_u8, 0x99, 0xff]);
 
     assert_eq!(format!("{}", hex), "0199FF");
-    assert_eq!(format!("{:?}", hex), "\"0199FF\"");
+    assert_eq!(format!("{:?}", hex), "0199FF");
     assert_eq!(format!("{:x}", hex), "0199ff");
     assert_eq!(format!("{:X}", hex), "0199FF");
 }
+
+#[test]
+fn test_width() {
+    let hex = Hex([1_u8, 0x99, 0xff]);
+
+    assert_eq!(format!("{:10}", hex), "0199ff    ");
+    assert_eq!(format!("{:<10}", hex), "0199ff    ");
+    assert_eq!(format!("{:>10}", hex), "    0199ff");
+    assert_eq!(format!("{:^10}", hex), "  0199ff  ");
+    assert_eq!(format!("{:^9}", hex), " 0199ff  ");
+    assert_eq!(format!("{:*>10}", hex), "****0199ff");
+    assert_eq!(format!("{:-^10x}", hex), "--0199ff--");
+    assert_eq!(format!("{:>10X}", hex), "    0199FF");
+    assert_eq!(format!("{:>10?}", hex), "    0199ff");
+    assert_eq!(format!("{:4}", hex), "0199ff");
+}
+
+#[test]
+fn test_zero_pad() {
+    let hex = Hex([1_u8, 0x99, 0xff]);
+
+    assert_eq!(format!("{:010}", hex), "00000199ff");
+    assert_eq!(format!("{:010x}", hex), "00000199ff");
+    assert_eq!(format!("{:010X}", hex), "00000199FF");
+    assert_eq!(format!("{:04x}", hex), "0199ff");
+    // the zero flag takes precedence over alignment, like for integers
+    assert_eq!(format!("{:<010x}", hex), "00000199ff");
+
+    let hex = UpperHex([0xab_u8]);
+    assert_eq!(format!("{:08}", hex), "000000AB");
+    assert_eq!(format!("{:08x}", hex), "000000ab");
+}
+
+#[test]
+fn test_precision() {
+    let hex = Hex([1_u8, 0x99, 0xff]);
+
+    assert_eq!(format!("{:.0}", hex), "");
+    assert_eq!(format!("{:.1}", hex), "01");
+    assert_eq!(format!("{:.2}", hex), "0199");
+    assert_eq!(format!("{:.3}", hex), "0199ff");
+    assert_eq!(format!("{:.10}", hex), "0199ff");
+    assert_eq!(format!("{:.2X}", hex), "0199");
+    assert_eq!(format!("{:.2?}", hex), "0199");
+    assert_eq!(format!("{:>8.2}", hex), "    0199");
+    assert_eq!(format!("{:08.1x}", hex), "00000001");
+}
+
+#[test]
+fn test_empty() {
+    let hex = Hex(Vec::<u8>::new());
+
+    assert_eq!(format!("{}", hex), "");
+    assert_eq!(format!("{:>4}", hex), "    ");
+    assert_eq!(format!("{:04}", hex), "0000");
+}
+
+#[test]
+fn test_unsized() {
+    let data: &[u8] = &[1, 0x99, 0xff];
+    let hex: &Hex<[u8]> = data.into();
+
+    assert_eq!(format!("{:>8}", hex), "  0199ff");
+    assert_eq!(format!("{:.1}", hex), "01");
+
+    let hex: &UpperHex<str> = crate::HexExt::as_upper_hex("hi");
+    assert_eq!(format!("{hex}"), "6869");
+}
+
+#[test]
+fn test_debug_alternate() {
+    let hex = Hex([1_u8, 0x99, 0xff]);
+    assert_eq!(
+        format!("{:#?}", hex),
+        "Hex {\n    len: 3,\n    data: [\n        0199ff,\n    ],\n}"
+    );
+
+    let hex = UpperHex((0..20).collect::<Vec<u8>>());
+    assert_eq!(
+        format!("{:#?}", hex),
+        "UpperHex {\n    len: 20,\n    data: [\n        \
+         000102030405060708090A0B0C0D0E0F,\n        \
+         10111213,\n    ],\n}"
+    );
+
+    let hex = Hex((0..20).collect::<Vec<u8>>());
+    assert_eq!(
+        format!("{:#.2?}", hex),
+        "Hex {\n    len: 20,\n    data: [\n        0001,\n    ],\n}"
+    );
+
+    let hex = Hex(Vec::<u8>::new());
+    assert_eq!(
+        format!("{:#?}", hex),
+        "Hex {\n    len: 0,\n    data: [],\n}"
+    );
+}
+
+#[test]
+fn test_debug_nested() {
+    #[derive(Debug)]
+    struct Test {
+        id: Hex<[u8; 2]>,
+    }
+
+    let test = Test {
+        id: Hex([0xde, 0xad]),
+    };
+    assert_eq!(format!("{:?}", test), "Test { id: dead }");
+    assert_eq!(format!("{:x}", test.id), "dead");
+}
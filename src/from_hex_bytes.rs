@@ -0,0 +1,397 @@
+use alloc::{boxed::Box, rc::Rc, string::String, sync::Arc, vec, vec::Vec};
+
+use crate::decode::{decode_in_place, fast_deserialize_into};
+
+/// Byte containers that a hex string can be decoded into.
+///
+/// Decoding asks the type for a destination buffer of the decoded length,
+/// decodes the hex string directly into it, and then converts the filled
+/// buffer into the final value. This allows most types to be decoded in
+/// place, without any intermediate copy.
+///
+/// This is implemented for the common byte containers of `std`, and, behind
+/// features of the same name, for the containers of `generic-array`,
+/// `heapless`, `arrayvec`, `smallvec` and `bytes`.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_hex::{FromHexBytes, Hex};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Key([u8; 4]);
+///
+/// impl FromHexBytes for Key {
+///     type Buffer = [u8; 4];
+///
+///     fn expected_len() -> Option<usize> {
+///         Some(4)
+///     }
+///
+///     fn buffer(len: usize) -> Option<Self::Buffer> {
+///         (len == 4).then_some([0; 4])
+///     }
+///
+///     fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+///         Some(Key(buffer))
+///     }
+/// }
+///
+/// let key: Hex<Key> = "deadbeef".parse().unwrap();
+/// assert_eq!(key.0, Key([0xde, 0xad, 0xbe, 0xef]));
+/// ```
+pub trait FromHexBytes: Sized {
+    /// Destination buffer the bytes get decoded into.
+    type Buffer: AsMut<[u8]>;
+
+    /// Number of bytes this type expects, if it only supports a single length.
+    fn expected_len() -> Option<usize> {
+        None
+    }
+
+    /// Creates a destination buffer for `len` decoded bytes.
+    ///
+    /// Returns `None` if the type can not hold `len` bytes.
+    fn buffer(len: usize) -> Option<Self::Buffer>;
+
+    /// Converts a filled destination buffer into the final value.
+    ///
+    /// Returns `None` if the bytes are not a valid value. Decoding reports
+    /// this as `FromHexError::InvalidStringLength`, like a length that
+    /// `buffer` rejects.
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self>;
+
+    /// Decodes an owned hex string, which allows reusing its allocation.
+    ///
+    /// On failure the string is returned unchanged, so the caller can report
+    /// the error for the original input. The default implementation decodes
+    /// into a new destination buffer, like for borrowed strings. `Vec<u8>`
+    /// and the containers that are created from it decode the string in
+    /// place instead.
+    fn from_hex_string(v: String) -> Result<Self, String> {
+        fast_deserialize_into(&v).map_err(|_| v)
+    }
+}
+
+/// Copies raw bytes into `T`, for sources that are not hex encoded.
+#[cfg(any(
+    feature = "serde",
+    feature = "sqlx",
+    feature = "diesel",
+    feature = "rusqlite"
+))]
+pub(crate) fn from_slice<T>(v: &[u8]) -> Option<T>
+where
+    T: FromHexBytes,
+{
+    let mut buf = T::buffer(v.len())?;
+    buf.as_mut().copy_from_slice(v);
+    T::from_buffer(buf)
+}
+
+impl<const N: usize> FromHexBytes for [u8; N] {
+    type Buffer = [u8; N];
+
+    fn expected_len() -> Option<usize> {
+        Some(N)
+    }
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        (len == N).then_some([0; N])
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer)
+    }
+}
+
+impl FromHexBytes for Vec<u8> {
+    type Buffer = Vec<u8>;
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        Some(vec![0; len])
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer)
+    }
+
+    fn from_hex_string(v: String) -> Result<Self, String> {
+        decode_in_place(v)
+    }
+}
+
+macro_rules! impl_from_vec {
+    ($($T:ty),*) => {
+        $(
+            impl FromHexBytes for $T {
+                type Buffer = Vec<u8>;
+
+                fn buffer(len: usize) -> Option<Self::Buffer> {
+                    Some(vec![0; len])
+                }
+
+                fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+                    Some(buffer.into())
+                }
+
+                fn from_hex_string(v: String) -> Result<Self, String> {
+                    decode_in_place(v).map(Into::into)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_vec!(Box<[u8]>, Rc<[u8]>, Arc<[u8]>);
+
+#[cfg(feature = "generic-array")]
+#[allow(deprecated)]
+impl<N> FromHexBytes for generic_array::GenericArray<u8, N>
+where
+    N: generic_array::ArrayLength<u8>,
+{
+    type Buffer = Self;
+
+    fn expected_len() -> Option<usize> {
+        Some(N::USIZE)
+    }
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        (len == N::USIZE).then(Self::default)
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer)
+    }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> FromHexBytes for heapless::Vec<u8, N> {
+    type Buffer = Self;
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        let mut buffer = Self::new();
+        buffer.resize(len, 0).ok()?;
+        Some(buffer)
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer)
+    }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const CAP: usize> FromHexBytes for arrayvec::ArrayVec<u8, CAP> {
+    type Buffer = Self;
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        (len <= CAP).then(|| core::iter::repeat_n(0, len).collect())
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A> FromHexBytes for smallvec::SmallVec<A>
+where
+    A: smallvec::Array<Item = u8>,
+{
+    type Buffer = Self;
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        Some(Self::from_elem(0, len))
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        Some(buffer)
+    }
+}
+
+/// Adapter for decoding into types that implement `TryFrom<&[u8]>`.
+///
+/// Decoding used to accept any `T: for<'a> TryFrom<&'a [u8]>`. Types that
+/// only implement that trait can still be decoded by wrapping them in this
+/// adapter, for example as `Hex<TryFromSlice<T>>`. The bytes are decoded
+/// into a temporary buffer first, and a failed conversion is reported as
+/// `FromHexError::InvalidStringLength`, like before.
+///
+/// # Example
+///
+/// ```rust
+/// use easy_hex::{Hex, TryFromSlice};
+///
+/// #[derive(Debug, PartialEq)]
+/// struct Key([u8; 2]);
+///
+/// impl TryFrom<&[u8]> for Key {
+///     type Error = ();
+///
+///     fn try_from(bytes: &[u8]) -> Result<Self, ()> {
+///         bytes.try_into().map(Key).map_err(|_| ())
+///     }
+/// }
+///
+/// let key: Hex<TryFromSlice<Key>> = "dead".parse().unwrap();
+/// assert_eq!(key.0 .0, Key([0xde, 0xad]));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TryFromSlice<T>(pub T);
+
+impl<T> FromHexBytes for TryFromSlice<T>
+where
+    T: for<'a> TryFrom<&'a [u8]>,
+{
+    type Buffer = Vec<u8>;
+
+    fn buffer(len: usize) -> Option<Self::Buffer> {
+        Some(vec![0; len])
+    }
+
+    fn from_buffer(buffer: Self::Buffer) -> Option<Self> {
+        T::try_from(&buffer).ok().map(TryFromSlice)
+    }
+}
+
+impl<T> AsRef<[u8]> for TryFromSlice<T>
+where
+    T: AsRef<[u8]>,
+{
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{decode_into, FromHexError};
+
+    use super::*;
+
+    #[test]
+    fn test_std() {
+        assert_eq!(decode_into::<[u8; 3]>("0199fF"), Ok([1, 0x99, 0xff]));
+        assert_eq!(decode_into::<Vec<u8>>("0199fF"), Ok(vec![1, 0x99, 0xff]));
+        assert_eq!(
+            decode_into::<Box<[u8]>>("0199fF"),
+            Ok(vec![1, 0x99, 0xff].into())
+        );
+        assert_eq!(
+            decode_into::<Rc<[u8]>>("0199fF"),
+            Ok(vec![1, 0x99, 0xff].into())
+        );
+        assert_eq!(
+            decode_into::<Arc<[u8]>>("0199fF"),
+            Ok(vec![1, 0x99, 0xff].into())
+        );
+        assert_eq!(<[u8; 3]>::expected_len(), Some(3));
+        assert_eq!(<Vec<u8>>::expected_len(), None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            decode_into::<[u8; 2]>("0199fF"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_into::<Vec<u8>>("0199f"),
+            Err(FromHexError::OddLength)
+        );
+        assert_eq!(
+            decode_into::<Vec<u8>>("01x9"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        #[derive(Debug, PartialEq)]
+        struct Even(Vec<u8>);
+
+        impl TryFrom<&[u8]> for Even {
+            type Error = ();
+
+            fn try_from(bytes: &[u8]) -> Result<Self, ()> {
+                match bytes.len() % 2 {
+                    0 => Ok(Even(bytes.to_vec())),
+                    _ => Err(()),
+                }
+            }
+        }
+
+        assert_eq!(
+            decode_into::<TryFromSlice<Even>>("0199"),
+            Ok(TryFromSlice(Even(vec![1, 0x99])))
+        );
+        assert_eq!(
+            decode_into::<TryFromSlice<Even>>("0199fF"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(
+            decode_into::<TryFromSlice<[u8; 2]>>("0199"),
+            Ok(TryFromSlice([1, 0x99]))
+        );
+        assert_eq!(
+            "0199"
+                .parse::<crate::Hex<TryFromSlice<[u8; 2]>>>()
+                .unwrap()
+                .to_string(),
+            "0199"
+        );
+    }
+
+    #[cfg(feature = "generic-array")]
+    #[test]
+    #[allow(deprecated)]
+    fn test_generic_array() {
+        use generic_array::{typenum::U3, GenericArray};
+
+        assert_eq!(
+            decode_into::<GenericArray<u8, U3>>("0199fF"),
+            Ok([1, 0x99, 0xff].into())
+        );
+        assert_eq!(
+            decode_into::<GenericArray<u8, U3>>("01"),
+            Err(FromHexError::InvalidStringLength)
+        );
+        assert_eq!(GenericArray::<u8, U3>::expected_len(), Some(3));
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn test_heapless() {
+        type V = heapless::Vec<u8, 3>;
+
+        assert_eq!(&decode_into::<V>("0199fF").unwrap()[..], [1, 0x99, 0xff]);
+        assert_eq!(&decode_into::<V>("01").unwrap()[..], [1]);
+        assert_eq!(
+            decode_into::<V>("01020304"),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn test_arrayvec() {
+        type V = arrayvec::ArrayVec<u8, 3>;
+
+        assert_eq!(&decode_into::<V>("0199fF").unwrap()[..], [1, 0x99, 0xff]);
+        assert!(decode_into::<V>("").unwrap().is_empty());
+        assert_eq!(
+            decode_into::<V>("01020304"),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn test_smallvec() {
+        type V = smallvec::SmallVec<[u8; 2]>;
+
+        assert_eq!(&decode_into::<V>("0199").unwrap()[..], [1, 0x99]);
+        assert_eq!(&decode_into::<V>("0199fF").unwrap()[..], [1, 0x99, 0xff]);
+    }
+}
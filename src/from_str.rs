@@ -1,10 +1,10 @@
-use std::str::FromStr;
+use core::str::FromStr;
 
-use crate::{decode_into, FromHexError, Hex, UpperHex};
+use crate::{decode_into, FromHexBytes, FromHexError, Hex, UpperHex};
 
 impl<T> FromStr for Hex<T>
 where
-    T: for<'a> TryFrom<&'a [u8]>,
+    T: FromHexBytes,
 {
     type Err = FromHexError;
 
@@ -15,7 +15,7 @@ where
 
 impl<T> FromStr for UpperHex<T>
 where
-    T: for<'a> TryFrom<&'a [u8]>,
+    T: FromHexBytes,
 {
     type Err = FromHexError;
 
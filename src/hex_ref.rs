@@ -0,0 +1,233 @@
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+use hex::FromHexError;
+
+use crate::{
+    decode::{fast_deserialize, fast_deserialize_into},
+    FromHexBytes,
+};
+
+/// Borrowed, validated hex string that is decoded on demand.
+///
+/// In contrast to `Hex<T>`, deserializing this type does not decode
+/// anything. It keeps a reference to the hex string in the input,
+/// which allows decoding it later directly into the final byte container
+/// without any intermediate buffer.
+///
+/// Deserialization requires a deserializer that can lend out borrowed
+/// strings, like `serde_json::from_str`. Hex strings that contain escape
+/// sequences can not be borrowed, and will fail to deserialize.
+///
+/// ```rust
+/// use easy_hex::HexRef;
+///
+/// let hex: HexRef = serde_json::from_str(r#""0110ff""#).unwrap();
+/// assert_eq!(hex.as_str(), "0110ff");
+/// assert_eq!(hex.to_vec(), [1, 16, 255]);
+/// ```
+#[derive(Copy, Clone, PartialOrd, Ord, Hash, Eq, PartialEq)]
+pub struct HexRef<'a>(&'a str);
+
+impl<'a> HexRef<'a> {
+    /// Validates a hex string, without decoding it.
+    ///
+    /// This accepts both lower and upper case strings.
+    pub fn new(v: &'a str) -> Result<Self, FromHexError> {
+        if !v.len().is_multiple_of(2) {
+            return Err(FromHexError::OddLength);
+        }
+        if let Some(index) = v.bytes().position(|c| !c.is_ascii_hexdigit()) {
+            let c = v[index..].chars().next().unwrap_or_default();
+            return Err(FromHexError::InvalidHexCharacter { c, index });
+        }
+        Ok(Self(v))
+    }
+
+    /// Returns the borrowed hex string.
+    pub fn as_str(&self) -> &'a str {
+        self.0
+    }
+
+    /// Returns the number of bytes the hex string decodes to.
+    pub fn len(&self) -> usize {
+        self.0.len() / 2
+    }
+
+    /// Returns `true` if the hex string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Decodes the hex string to a byte slice.
+    ///
+    /// The resulting byte slice is passed to the closure.
+    pub fn decode<V>(&self, out: impl FnOnce(&[u8]) -> V) -> V {
+        // NB: This can never fail, as the string has been validated
+        fast_deserialize(self.0, out).expect("validated hex string")
+    }
+
+    /// Decodes the hex string to a byte container.
+    pub fn decode_into<T>(&self) -> Result<T, FromHexError>
+    where
+        T: FromHexBytes,
+    {
+        fast_deserialize_into(self.0)
+    }
+
+    /// Decodes the hex string directly into a new vector.
+    pub fn to_vec(&self) -> Vec<u8> {
+        // NB: This can never fail, as the string has been validated
+        fast_deserialize_into(self.0).expect("validated hex string")
+    }
+
+    /// Decodes the hex string directly into a new boxed slice.
+    pub fn to_boxed_slice(&self) -> Box<[u8]> {
+        self.to_vec().into_boxed_slice()
+    }
+}
+
+impl<'a> TryFrom<&'a str> for HexRef<'a> {
+    type Error = FromHexError;
+
+    fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl AsRef<str> for HexRef<'_> {
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Display for HexRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+impl fmt::Debug for HexRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use core::{fmt, marker::PhantomData};
+
+    use serde::{
+        de::{Error, Unexpected, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::HexRef;
+
+    impl Serialize for HexRef<'_> {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serializer.serialize_str(self.0)
+        }
+    }
+
+    struct Vis<'a>(PhantomData<&'a str>);
+    impl<'de: 'a, 'a> Visitor<'de> for Vis<'a> {
+        type Value = HexRef<'a>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a borrowed hexadecimal string")
+        }
+
+        fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E>
+        where
+            E: Error,
+        {
+            HexRef::new(v).map_err(|_| Error::invalid_value(Unexpected::Str(v), &self))
+        }
+    }
+
+    impl<'de: 'a, 'a> Deserialize<'de> for HexRef<'a> {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserializer.deserialize_str(Vis(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let hex = HexRef::new("0199fF").unwrap();
+        assert_eq!(hex.as_str(), "0199fF");
+        assert_eq!(hex.len(), 3);
+        assert!(!hex.is_empty());
+        assert!(HexRef::new("").unwrap().is_empty());
+
+        assert_eq!(HexRef::new("019"), Err(FromHexError::OddLength));
+        assert_eq!(
+            HexRef::new("01x9"),
+            Err(FromHexError::InvalidHexCharacter { c: 'x', index: 2 })
+        );
+        assert_eq!(
+            HexRef::new("01ä"),
+            Err(FromHexError::InvalidHexCharacter { c: 'ä', index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_decode() {
+        let hex = HexRef::new("0199fF").unwrap();
+        assert_eq!(hex.to_vec(), [1, 0x99, 0xff]);
+        assert_eq!(&*hex.to_boxed_slice(), [1, 0x99, 0xff]);
+        assert_eq!(hex.decode(|b| b.to_vec()), [1, 0x99, 0xff]);
+        assert_eq!(hex.decode_into::<[u8; 3]>(), Ok([1, 0x99, 0xff]));
+        assert_eq!(
+            hex.decode_into::<[u8; 2]>(),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[test]
+    fn test_fmt() {
+        let hex = HexRef::new("0199fF").unwrap();
+        assert_eq!(format!("{}", hex), "0199fF");
+        assert_eq!(format!("{:?}", hex), "0199fF");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use crate::tests::to_json;
+
+        #[derive(serde_derive::Deserialize)]
+        struct Test<'a> {
+            #[serde(borrow)]
+            data: HexRef<'a>,
+        }
+
+        let json = r#"{"data":"0199fF"}"#;
+        let test: Test = serde_json::from_str(json).unwrap();
+        assert_eq!(test.data.as_str(), "0199fF");
+        assert_eq!(test.data.to_vec(), [1, 0x99, 0xff]);
+        assert_eq!(to_json(&test.data), json);
+
+        // large payloads borrow the input as well
+        let long = "ab".repeat(1000);
+        let json = format!(r#"{{"data":"{long}"}}"#);
+        let test: Test = serde_json::from_str(&json).unwrap();
+        assert_eq!(test.data.to_vec(), vec![0xab; 1000]);
+
+        assert!(serde_json::from_str::<Test>(r#"{"data":"019"}"#).is_err());
+        assert!(serde_json::from_str::<Test>(r#"{"data":"01zz"}"#).is_err());
+        // escaped strings can not be borrowed
+        assert!(serde_json::from_str::<Test>(r#"{"data":"\u00301"}"#).is_err());
+    }
+}
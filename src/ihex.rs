@@ -0,0 +1,398 @@
+//! Intel HEX firmware files.
+//!
+//! ```rust
+//! use easy_hex::ihex::IntelHex;
+//!
+//! let image = IntelHex::parse(":0400100001020304E2\n:00000001FF\n").unwrap();
+//! assert_eq!(image.memory.get(0x12), Some(3));
+//! assert_eq!(image.encode(16), ":0400100001020304E2\n:00000001FF\n");
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::{error::Error, fmt, str::FromStr};
+
+use crate::{
+    decode::fast_deserialize_into, encode::fast_serialize, FromHexError, MemoryMap, UPPER,
+};
+
+const DATA: u8 = 0x00;
+const END_OF_FILE: u8 = 0x01;
+const EXTENDED_SEGMENT_ADDRESS: u8 = 0x02;
+const START_SEGMENT_ADDRESS: u8 = 0x03;
+const EXTENDED_LINEAR_ADDRESS: u8 = 0x04;
+const START_LINEAR_ADDRESS: u8 = 0x05;
+
+/// Execution start address of an Intel HEX file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StartAddress {
+    /// `CS:IP` register values for 80x86 processors.
+    Segment { cs: u16, ip: u16 },
+    /// `EIP` register value for 80386 and later processors.
+    Linear(u32),
+}
+
+/// Contents of an Intel HEX file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IntelHex {
+    /// The data records.
+    pub memory: MemoryMap,
+    /// The start address record, if any.
+    pub start: Option<StartAddress>,
+}
+
+/// Error returned when parsing an Intel HEX file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// A record is malformed, or its length does not match its type.
+    InvalidRecord { line: usize },
+    /// A record contains invalid hex data.
+    InvalidHex { line: usize, error: FromHexError },
+    /// The checksum of a record does not match its contents.
+    InvalidChecksum {
+        line: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// A record has an unknown type.
+    UnknownRecordType { line: usize, record_type: u8 },
+    /// A data record extends beyond the 32 bit address space.
+    AddressOverflow { line: usize },
+    /// The file contains records after the end of file record.
+    DataAfterEndOfFile { line: usize },
+    /// The file does not end with an end of file record.
+    MissingEndOfFile,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord { line } => write!(f, "Invalid record in line {line}"),
+            Self::InvalidHex { line, error } => write!(f, "{error} in line {line}"),
+            Self::InvalidChecksum {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Invalid checksum {found:02X} in line {line}, expected {expected:02X}"
+            ),
+            Self::UnknownRecordType { line, record_type } => {
+                write!(f, "Unknown record type {record_type:02X} in line {line}")
+            }
+            Self::AddressOverflow { line } => write!(f, "Address overflow in line {line}"),
+            Self::DataAfterEndOfFile { line } => {
+                write!(f, "Record after end of file in line {line}")
+            }
+            Self::MissingEndOfFile => write!(f, "Missing end of file record"),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .fold(0u8, |sum, &b| sum.wrapping_add(b))
+        .wrapping_neg()
+}
+
+fn push_record(out: &mut String, record_type: u8, address: u16, data: &[u8]) {
+    debug_assert!(data.len() <= 0xff);
+
+    let mut bytes = Vec::with_capacity(data.len() + 5);
+    bytes.push(data.len() as u8);
+    bytes.extend_from_slice(&address.to_be_bytes());
+    bytes.push(record_type);
+    bytes.extend_from_slice(data);
+    bytes.push(checksum(&bytes));
+
+    out.push(':');
+    fast_serialize::<_, _, UPPER>(&bytes, |hex| out.push_str(hex));
+    out.push('\n');
+}
+
+impl IntelHex {
+    /// Parses the records of an Intel HEX file.
+    ///
+    /// Checksums are validated, and the file has to end with an end of file
+    /// record. Empty lines are ignored.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut image = Self::default();
+        let mut base = 0u32;
+        let mut eof = false;
+
+        for (i, text) in input.lines().enumerate() {
+            let line = i + 1;
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            if eof {
+                return Err(ParseError::DataAfterEndOfFile { line });
+            }
+
+            let hex = text
+                .strip_prefix(':')
+                .ok_or(ParseError::InvalidRecord { line })?;
+            let bytes: Vec<u8> = fast_deserialize_into(hex)
+                .map_err(|error| ParseError::InvalidHex { line, error })?;
+            if bytes.len() < 5 || bytes.len() != usize::from(bytes[0]) + 5 {
+                return Err(ParseError::InvalidRecord { line });
+            }
+
+            let (record, found) = bytes.split_at(bytes.len() - 1);
+            let (found, expected) = (found[0], checksum(record));
+            if found != expected {
+                return Err(ParseError::InvalidChecksum {
+                    line,
+                    expected,
+                    found,
+                });
+            }
+
+            let offset = u16::from_be_bytes([record[1], record[2]]);
+            let record_type = record[3];
+            let data = &record[4..];
+            let invalid = ParseError::InvalidRecord { line };
+            match (record_type, data.len()) {
+                (DATA, _) => {
+                    let address = u64::from(base) + u64::from(offset);
+                    if address + data.len() as u64 > 1 << 32 {
+                        return Err(ParseError::AddressOverflow { line });
+                    }
+                    image.memory.insert(address as u32, data);
+                }
+                (END_OF_FILE, 0) => eof = true,
+                (EXTENDED_SEGMENT_ADDRESS, 2) => {
+                    base = u32::from(u16::from_be_bytes([data[0], data[1]])) << 4;
+                }
+                (START_SEGMENT_ADDRESS, 4) => {
+                    image.start = Some(StartAddress::Segment {
+                        cs: u16::from_be_bytes([data[0], data[1]]),
+                        ip: u16::from_be_bytes([data[2], data[3]]),
+                    });
+                }
+                (EXTENDED_LINEAR_ADDRESS, 2) => {
+                    base = u32::from(u16::from_be_bytes([data[0], data[1]])) << 16;
+                }
+                (START_LINEAR_ADDRESS, 4) => {
+                    image.start = Some(StartAddress::Linear(u32::from_be_bytes([
+                        data[0], data[1], data[2], data[3],
+                    ])));
+                }
+                (END_OF_FILE..=START_LINEAR_ADDRESS, _) => return Err(invalid),
+                (record_type, _) => {
+                    return Err(ParseError::UnknownRecordType { line, record_type })
+                }
+            }
+        }
+
+        if !eof {
+            return Err(ParseError::MissingEndOfFile);
+        }
+        Ok(image)
+    }
+
+    /// Writes the memory map as Intel HEX records with up to `record_len`
+    /// data bytes each.
+    ///
+    /// Addresses above 64 KiB use extended linear address records.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `record_len` is `0`.
+    pub fn encode(&self, record_len: u8) -> String {
+        assert!(record_len > 0, "record length must not be 0");
+
+        let mut out = String::new();
+        let mut upper = 0u16;
+        for (start, data) in self.memory.segments() {
+            let mut address = start;
+            let mut rest = data;
+            while !rest.is_empty() {
+                let (high, low) = ((address >> 16) as u16, address as u16);
+                if high != upper {
+                    push_record(&mut out, EXTENDED_LINEAR_ADDRESS, 0, &high.to_be_bytes());
+                    upper = high;
+                }
+
+                // records must not cross a 64 KiB boundary
+                let n = rest
+                    .len()
+                    .min(usize::from(record_len))
+                    .min(0x10000 - usize::from(low));
+                push_record(&mut out, DATA, low, &rest[..n]);
+                rest = &rest[n..];
+                address = address.wrapping_add(n as u32);
+            }
+        }
+
+        match self.start {
+            Some(StartAddress::Segment { cs, ip }) => {
+                let data = [cs.to_be_bytes(), ip.to_be_bytes()].concat();
+                push_record(&mut out, START_SEGMENT_ADDRESS, 0, &data);
+            }
+            Some(StartAddress::Linear(eip)) => {
+                push_record(&mut out, START_LINEAR_ADDRESS, 0, &eip.to_be_bytes());
+            }
+            None => {}
+        }
+        push_record(&mut out, END_OF_FILE, 0, &[]);
+        out
+    }
+}
+
+impl FromStr for IntelHex {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Writes records of 16 bytes, see [`IntelHex::encode`].
+impl fmt::Display for IntelHex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode(16))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "\
+:10010000214601360121470136007EFE09D2190140
+:100110002146017E17C20001FF5F16002148011928
+:10012000194E79234623965778239EDA3F01B2CAA7
+:100130003F0156702B5E712B722B732146013421C7
+:00000001FF
+";
+        let image: IntelHex = input.parse().unwrap();
+        assert_eq!(image.start, None);
+        let segments: Vec<_> = image.memory.segments().collect();
+        assert_eq!(segments.len(), 1);
+        assert_eq!(segments[0].0, 0x100);
+        assert_eq!(segments[0].1.len(), 64);
+        assert_eq!(segments[0].1[..4], [0x21, 0x46, 0x01, 0x36]);
+
+        assert_eq!(image.encode(16), input);
+        assert_eq!(image.to_string(), input);
+    }
+
+    #[test]
+    fn test_addresses() {
+        let input = "\
+:020000021000EC
+:02000100AABB98
+:02000004ABCD82
+:02FFFF00CCDD57
+:0400000300001234B3
+:00000001FF
+";
+        let image = IntelHex::parse(input).unwrap();
+        assert_eq!(image.memory.get(0x10001), Some(0xaa));
+        assert_eq!(image.memory.get(0xabcdffff), Some(0xcc));
+        assert_eq!(image.memory.get(0xabce0000), Some(0xdd));
+        assert_eq!(
+            image.start,
+            Some(StartAddress::Segment { cs: 0, ip: 0x1234 })
+        );
+
+        // the record crossing 64 KiB gets split
+        assert_eq!(
+            image.encode(16),
+            "\
+:020000040001F9
+:02000100AABB98
+:02000004ABCD82
+:01FFFF00CC35
+:02000004ABCE81
+:01000000DD22
+:0400000300001234B3
+:00000001FF
+"
+        );
+    }
+
+    #[test]
+    fn test_encode() {
+        let mut image = IntelHex {
+            start: Some(StartAddress::Linear(0x08000000)),
+            ..Default::default()
+        };
+        image.memory.insert(0x0800_0000, &[1, 2, 3, 4, 5]);
+        let encoded = image.encode(2);
+        assert_eq!(
+            encoded,
+            "\
+:020000040800F2
+:020000000102FB
+:020002000304F5
+:0100040005F6
+:0400000508000000EF
+:00000001FF
+"
+        );
+        assert_eq!(IntelHex::parse(&encoded).unwrap(), image);
+    }
+
+    #[test]
+    fn test_errors() {
+        let parse = |s: &str| IntelHex::parse(s).unwrap_err();
+
+        assert_eq!(parse(""), ParseError::MissingEndOfFile);
+        assert_eq!(parse("00000001FF"), ParseError::InvalidRecord { line: 1 });
+        assert_eq!(parse(":000000FF"), ParseError::InvalidRecord { line: 1 });
+        assert_eq!(
+            parse(":0200000001FF"),
+            ParseError::InvalidRecord { line: 1 }
+        );
+        assert_eq!(
+            parse(":0100000100FE"),
+            ParseError::InvalidRecord { line: 1 }
+        );
+        assert_eq!(
+            parse(":00000001F"),
+            ParseError::InvalidHex {
+                line: 1,
+                error: FromHexError::OddLength
+            }
+        );
+        assert_eq!(
+            parse(":00000001FE"),
+            ParseError::InvalidChecksum {
+                line: 1,
+                expected: 0xff,
+                found: 0xfe
+            }
+        );
+        assert_eq!(
+            parse(":00000006FA"),
+            ParseError::UnknownRecordType {
+                line: 1,
+                record_type: 6
+            }
+        );
+        assert_eq!(
+            parse(":02000004FFFFFC\n:02FFFF000102FD"),
+            ParseError::AddressOverflow { line: 2 }
+        );
+        assert_eq!(
+            parse(":00000001FF\n:00000001FF"),
+            ParseError::DataAfterEndOfFile { line: 2 }
+        );
+        assert_eq!(
+            ParseError::InvalidChecksum {
+                line: 1,
+                expected: 0xff,
+                found: 0xfe
+            }
+            .to_string(),
+            "Invalid checksum FE in line 1, expected FF"
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use alloc::vec::Vec;
+use core::{fmt, marker::PhantomData};
+
+use serde::{
+    de::{Error, SeqAccess, Unexpected, Visitor},
+    Deserialize, Deserializer,
+};
+
+use crate::{decode::fast_deserialize_into, from_hex_bytes::from_slice, FromHexBytes, LenientHex};
+
+struct Vis<T>(PhantomData<T>);
+impl<'a, T> Visitor<'a> for Vis<T>
+where
+    T: FromHexBytes,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "a hexadecimal string or a sequence of bytes")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        let value: T = fast_deserialize_into::<T>(v)
+            .map_err(|_| Error::invalid_type(Unexpected::Str(v), &self))?;
+        Ok(value)
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+    where
+        E: Error,
+    {
+        from_slice(v).ok_or_else(|| Error::invalid_length(v.len(), &self))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'a>,
+    {
+        let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(byte) = seq.next_element::<u8>()? {
+            bytes.push(byte);
+        }
+        from_slice(&bytes).ok_or_else(|| Error::invalid_length(bytes.len(), &self))
+    }
+}
+
+impl<'a, T> Deserialize<'a> for LenientHex<T>
+where
+    T: FromHexBytes,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'a>,
+    {
+        deserializer
+            .deserialize_any(Vis(PhantomData))
+            .map(LenientHex)
+    }
+}
+
+/// Deserialize function for a hex string, a sequence of bytes, or
+/// serde bytes.
+pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromHexBytes,
+{
+    LenientHex::<T>::deserialize(deserializer).map(|v| v.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_derive::{Deserialize, Serialize};
+
+    use crate::tests::{from_json, to_json};
+
+    use super::*;
+
+    #[test]
+    fn test_from_str() {
+        let hex: LenientHex<_> = from_json(r#"{"data":"0199fF"}"#);
+        assert_eq!(hex, LenientHex([1_u8, 0x99, 0xff]));
+    }
+
+    #[test]
+    fn test_from_seq() {
+        let hex: LenientHex<_> = from_json(r#"{"data":[1,153,255]}"#);
+        assert_eq!(hex, LenientHex([1_u8, 0x99, 0xff]));
+
+        let hex: LenientHex<Vec<u8>> = from_json(r#"{"data":[]}"#);
+        assert_eq!(hex, LenientHex(vec![]));
+    }
+
+    #[test]
+    fn test_from_bytes() {
+        use serde::de::value::{BytesDeserializer, Error};
+
+        let de = BytesDeserializer::<Error>::new(&[1, 2]);
+        let hex = LenientHex::<Vec<u8>>::deserialize(de).unwrap();
+        assert_eq!(hex, LenientHex(vec![1, 2]));
+    }
+
+    #[test]
+    fn test_errors() {
+        let parse = |json| serde_json::from_str::<LenientHex<[u8; 2]>>(json);
+        assert!(parse(r#""019""#).is_err());
+        assert!(parse(r#""zz00""#).is_err());
+        assert!(parse(r#"[1,2,3]"#).is_err());
+        assert!(parse(r#"[1,256]"#).is_err());
+        assert!(parse(r#"[1,-1]"#).is_err());
+        assert!(parse(r#"12"#).is_err());
+        assert!(parse(r#"[1,2]"#).is_ok());
+    }
+
+    #[test]
+    fn test_with() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Test {
+            #[serde(with = "crate::serde::lenient")]
+            a: Vec<u8>,
+            b: LenientHex<[u8; 2]>,
+        }
+
+        let test: Test = serde_json::from_str(r#"{"a":[1,2],"b":"ABCD"}"#).unwrap();
+        assert_eq!(
+            test,
+            Test {
+                a: vec![1, 2],
+                b: LenientHex([0xab, 0xcd])
+            }
+        );
+        // serialization always produces hex strings
+        assert_eq!(
+            serde_json::to_string(&test).unwrap(),
+            r#"{"a":"0102","b":"abcd"}"#
+        );
+    }
+
+    #[test]
+    fn test_strict_hex() {
+        assert!(serde_json::from_str::<crate::Hex<Vec<u8>>>("[1,2]").is_err());
+        assert_eq!(to_json(&LenientHex([1_u8])), r#"{"data":"01"}"#);
+    }
+}
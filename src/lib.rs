@@ -1,21 +1,81 @@
 #![doc = include_str!("../README.md")]
+// NB: the `TransparentWrapper` derive of `bytemuck` repeats the `?Sized`
+// bound of the wrapper types in a `where` clause. The lint fires on the
+// generated impls, so it can not be allowed on the types themselves.
+#![allow(clippy::multiple_bound_locations)]
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 
+extern crate alloc;
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+#[cfg(feature = "borsh")]
+mod borsh;
+#[cfg(feature = "bytes")]
+mod bytes;
+#[cfg(feature = "clap")]
+mod clap;
+mod cmp;
 mod decode;
+#[cfg(feature = "defmt")]
+mod defmt;
+#[cfg(feature = "diesel")]
+mod diesel;
+mod diff;
+mod dump;
 mod encode;
 
 mod fmt;
+mod from_hex_bytes;
 mod from_str;
+mod hex_ref;
+pub mod ihex;
+mod memory_map;
+#[cfg(feature = "parity-scale-codec")]
+mod parity_scale_codec;
+mod parse_dump;
+#[cfg(feature = "proptest")]
+pub mod proptest;
+#[cfg(feature = "quickcheck")]
+mod quickcheck;
+#[cfg(feature = "rkyv")]
+mod rkyv;
+#[cfg(feature = "rusqlite")]
+pub mod rusqlite;
+#[cfg(feature = "sqlx")]
+mod sqlx;
+pub mod srec;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "subtle")]
+mod subtle;
+#[cfg(feature = "tracing")]
+pub mod tracing;
+#[cfg(feature = "ufmt")]
+mod ufmt;
+#[cfg(feature = "valuable")]
+pub mod valuable;
+#[cfg(feature = "zerocopy")]
+mod zerocopy;
 
+#[cfg(feature = "serde")]
+mod collections;
 #[cfg(feature = "serde")]
 mod deserialize;
 #[cfg(feature = "serde")]
+mod lenient;
+#[cfg(feature = "serde_with")]
+mod serde_as;
+#[cfg(feature = "serde")]
 mod serialize;
 
 #[cfg(test)]
 mod tests;
 
-use std::ops::{Deref, DerefMut};
+use core::ops::{Deref, DerefMut};
 
+#[cfg(feature = "zerocopy")]
+use ::zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
 #[cfg(feature = "bytemuck")]
 use bytemuck::{Pod, TransparentWrapper, Zeroable};
 
@@ -24,12 +84,17 @@ use bytemuck::{Pod, TransparentWrapper, Zeroable};
 /// This is a simple wrapper around a sequence of bytes `T` that will be serialized,
 /// deserialized and formatted as a lowercase hexadecimal string.
 ///
-/// The type has a transparent representation, and implements the
-/// relevant `bytemuck` traits, which allows using it even in situations
-/// where you do not have ownership of the `T`.
+/// The type has a transparent representation, which allows casting
+/// references to `T` into references to the wrapper with `HexExt` or `From`,
+/// even if `T` is dynamically sized. With the `bytemuck` feature it also
+/// implements the relevant `bytemuck` traits.
 #[derive(Copy, Clone, Default, PartialOrd, Ord, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "bytemuck", derive(TransparentWrapper, Pod, Zeroable))]
 #[cfg_attr(feature = "bytemuck", transparent(T))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)
+)]
 #[repr(transparent)]
 pub struct Hex<T: ?Sized>(pub T);
 
@@ -38,15 +103,58 @@ pub struct Hex<T: ?Sized>(pub T);
 /// This is a simple wrapper around a sequence of bytes `T` that will be serialized,
 /// deserialized and formatted as a uppercase hexadecimal string.
 ///
-/// The type has a transparent representation, and implements the
-/// relevant `bytemuck` traits, which allows using it even in situations
-/// where you do not have ownership of the `T`.
+/// The type has a transparent representation, which allows casting
+/// references to `T` into references to the wrapper with `HexExt` or `From`,
+/// even if `T` is dynamically sized. With the `bytemuck` feature it also
+/// implements the relevant `bytemuck` traits.
 #[derive(Copy, Clone, Default, PartialOrd, Ord, Hash, Eq, PartialEq)]
 #[cfg_attr(feature = "bytemuck", derive(TransparentWrapper, Pod, Zeroable))]
 #[cfg_attr(feature = "bytemuck", transparent(T))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)
+)]
 #[repr(transparent)]
 pub struct UpperHex<T: ?Sized>(pub T);
 
+/// Lowercase hex serialization of bytes `T`, with lenient deserialization.
+///
+/// This behaves like `Hex`, but in addition to hex strings it also
+/// deserializes from sequences of bytes (like the JSON array `[1, 153, 255]`)
+/// and from serde bytes. This is useful to migrate data formats that
+/// used to encode bytes differently.
+///
+/// Serialization always produces a lowercase hex string.
+#[cfg(feature = "serde")]
+#[derive(Copy, Clone, Default, PartialOrd, Ord, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "bytemuck", derive(TransparentWrapper, Pod, Zeroable))]
+#[cfg_attr(feature = "bytemuck", transparent(T))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)
+)]
+#[repr(transparent)]
+pub struct LenientHex<T: ?Sized>(pub T);
+
+/// Lowercase hex serialization of bytes `T`, with constant-time comparison.
+///
+/// This behaves like `Hex`, but its `PartialEq` implementation compares the
+/// bytes in constant time with `subtle`, which makes it suitable for
+/// secrets like MACs or tokens. Only the length of the bytes can be
+/// observed. For the same reason, it does not implement `PartialOrd`.
+///
+/// Note that encoding and decoding are not constant-time.
+#[cfg(feature = "subtle")]
+#[derive(Copy, Clone, Default)]
+#[cfg_attr(feature = "bytemuck", derive(TransparentWrapper, Pod, Zeroable))]
+#[cfg_attr(feature = "bytemuck", transparent(T))]
+#[cfg_attr(
+    feature = "zerocopy",
+    derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)
+)]
+#[repr(transparent)]
+pub struct CtHex<T: ?Sized>(pub T);
+
 macro_rules! impl_basic {
     ($Hex:ident) => {
         // --- conversion traits ----------------
@@ -97,22 +205,23 @@ macro_rules! impl_basic {
                 Self(value)
             }
         }
-        #[cfg(feature = "bytemuck")]
         impl<'a, T> From<&'a T> for &'a $Hex<T>
         where
             T: ?Sized,
         {
             fn from(value: &'a T) -> Self {
-                TransparentWrapper::wrap_ref(value)
+                // SAFETY: the wrapper is `repr(transparent)` over `T`, so
+                // both have the same layout and pointer metadata.
+                unsafe { &*(value as *const T as *const $Hex<T>) }
             }
         }
-        #[cfg(feature = "bytemuck")]
         impl<'a, T> From<&'a mut T> for &'a mut $Hex<T>
         where
             T: ?Sized,
         {
             fn from(value: &'a mut T) -> Self {
-                TransparentWrapper::wrap_mut(value)
+                // SAFETY: see above, and the borrow is unique for `'a`.
+                unsafe { &mut *(value as *mut T as *mut $Hex<T>) }
             }
         }
     };
@@ -120,6 +229,10 @@ macro_rules! impl_basic {
 
 impl_basic!(Hex);
 impl_basic!(UpperHex);
+#[cfg(feature = "serde")]
+impl_basic!(LenientHex);
+#[cfg(feature = "subtle")]
+impl_basic!(CtHex);
 
 // Helper constants to make the usage of bools easier tor ead in thsi crate
 pub(crate) const LOWER: bool = false;
@@ -132,17 +245,13 @@ pub trait HexExt {
     fn into_hex(self) -> Hex<Self>
     where
         Self: Sized;
-    #[cfg(feature = "bytemuck")]
     fn as_hex(&self) -> &Hex<Self>;
-    #[cfg(feature = "bytemuck")]
     fn as_hex_mut(&mut self) -> &mut Hex<Self>;
 
     fn into_upper_hex(self) -> UpperHex<Self>
     where
         Self: Sized;
-    #[cfg(feature = "bytemuck")]
     fn as_upper_hex(&self) -> &UpperHex<Self>;
-    #[cfg(feature = "bytemuck")]
     fn as_upper_hex_mut(&mut self) -> &mut UpperHex<Self>;
 }
 
@@ -157,12 +266,10 @@ where
         self.into()
     }
 
-    #[cfg(feature = "bytemuck")]
     fn as_hex(&self) -> &Hex<Self> {
         self.into()
     }
 
-    #[cfg(feature = "bytemuck")]
     fn as_hex_mut(&mut self) -> &mut Hex<Self> {
         self.into()
     }
@@ -174,12 +281,10 @@ where
         self.into()
     }
 
-    #[cfg(feature = "bytemuck")]
     fn as_upper_hex(&self) -> &UpperHex<Self> {
         self.into()
     }
 
-    #[cfg(feature = "bytemuck")]
     fn as_upper_hex_mut(&mut self) -> &mut UpperHex<Self> {
         self.into()
     }
@@ -189,20 +294,48 @@ where
 /// functions for `Hex`. Can be used with `#[serde(with = "...")]`.
 #[cfg(feature = "serde")]
 pub mod serde {
+    pub use crate::collections::lower::{map_keys, option, seq};
     pub use crate::deserialize::deserialize;
     pub use crate::serialize::serialize;
+
+    /// Module that contains the serialization and deserialization
+    /// functions for `LenientHex`. Can be used with `#[serde(with = "...")]`.
+    pub mod lenient {
+        pub use crate::lenient::deserialize;
+        pub use crate::serialize::serialize;
+    }
 }
 
 /// Module that contains the serialization and deserialization
 /// functions for `UpperHex`. Can be used with `#[serde(with = "...")]`.
 #[cfg(feature = "serde")]
 pub mod serde_upper {
+    pub use crate::collections::upper::{map_keys, option, seq};
     pub use crate::deserialize::deserialize;
     pub use crate::serialize::serialize_upper as serialize;
 }
 
+#[cfg(feature = "serde_with")]
+pub use serde_as::{As, Case, Lower, Upper};
+
+#[cfg(feature = "bytes")]
+pub use crate::bytes::{decode_bytes, decode_bytes_mut};
+#[cfg(feature = "clap")]
+pub use crate::clap::{ArgExt, HexValueParser};
+#[cfg(feature = "rkyv")]
+pub use crate::rkyv::{ArchivedHex, ArchivedUpperHex};
+
 pub use decode::decode;
 pub use decode::decode_into;
+pub use diff::HexDiff;
+#[cfg(feature = "std")]
+pub use dump::DumpWriter;
 pub use encode::encode;
 pub use encode::encode_upper;
+pub use from_hex_bytes::{FromHexBytes, TryFromSlice};
 pub use hex::FromHexError;
+pub use hex_ref::HexRef;
+pub use memory_map::MemoryMap;
+pub use parse_dump::{parse_dump, parse_dump_as, DumpFormat, ParseDumpError};
+#[cfg(feature = "std")]
+pub use stream::{DecodeWriter, EncodeWriter};
@@ -0,0 +1,184 @@
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+/// Sparse memory image, as described by firmware files like Intel HEX.
+///
+/// The memory map covers the 32 bit address space, and stores the written
+/// bytes as contiguous segments. Adjacent and overlapping writes are merged.
+///
+/// ```rust
+/// use easy_hex::MemoryMap;
+///
+/// let mut memory = MemoryMap::new();
+/// memory.insert(0x100, &[1, 2]);
+/// memory.insert(0x102, &[3]);
+/// memory.insert(0x200, &[4]);
+///
+/// let segments: Vec<_> = memory.segments().collect();
+/// assert_eq!(segments, [(0x100, &[1, 2, 3][..]), (0x200, &[4][..])]);
+/// assert_eq!(memory.get(0x101), Some(2));
+/// assert_eq!(memory.get(0x103), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MemoryMap {
+    segments: BTreeMap<u32, Vec<u8>>,
+}
+
+fn segment_end(start: u32, data: &[u8]) -> u64 {
+    u64::from(start) + data.len() as u64
+}
+
+impl MemoryMap {
+    /// Creates an empty memory map.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes `data` starting at `address`, overwriting previous data.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the data extends beyond the 32 bit address space.
+    pub fn insert(&mut self, address: u32, data: &[u8]) {
+        let start = u64::from(address);
+        let end = start + data.len() as u64;
+        assert!(
+            end <= 1 << 32,
+            "data extends beyond the 32 bit address space"
+        );
+        if data.is_empty() {
+            return;
+        }
+
+        // segments are disjoint, so their ends are sorted as well
+        let last = u32::try_from(end).unwrap_or(u32::MAX);
+        let touching: Vec<u32> = self
+            .segments
+            .range(..=last)
+            .rev()
+            .take_while(|(&s, d)| segment_end(s, d) >= start)
+            .map(|(&s, _)| s)
+            .collect();
+
+        let mut merged_start = address;
+        let mut merged_end = end;
+        for &s in &touching {
+            merged_start = merged_start.min(s);
+            merged_end = merged_end.max(segment_end(s, &self.segments[&s]));
+        }
+
+        let mut buf = vec![0; (merged_end - u64::from(merged_start)) as usize];
+        for s in touching {
+            let old = self.segments.remove(&s).unwrap_or_default();
+            let offset = (s - merged_start) as usize;
+            buf[offset..][..old.len()].copy_from_slice(&old);
+        }
+        let offset = (address - merged_start) as usize;
+        buf[offset..][..data.len()].copy_from_slice(data);
+        self.segments.insert(merged_start, buf);
+    }
+
+    /// Returns the byte at `address`, if it has been written.
+    pub fn get(&self, address: u32) -> Option<u8> {
+        let (&start, data) = self.segments.range(..=address).next_back()?;
+        data.get((address - start) as usize).copied()
+    }
+
+    /// Iterates over the contiguous segments, ordered by address.
+    pub fn segments(&self) -> impl Iterator<Item = (u32, &[u8])> {
+        self.segments.iter().map(|(&s, d)| (s, &d[..]))
+    }
+
+    /// Returns the number of bytes that have been written.
+    pub fn len(&self) -> usize {
+        self.segments.values().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if no bytes have been written.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Returns the contents from the lowest to the highest written address,
+    /// with gaps filled by `fill`.
+    pub fn to_vec(&self, fill: u8) -> Vec<u8> {
+        let Some((&first, _)) = self.segments.first_key_value() else {
+            return Vec::new();
+        };
+        let mut out = Vec::new();
+        for (start, data) in self.segments() {
+            out.resize((start - first) as usize, fill);
+            out.extend_from_slice(data);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(memory: &MemoryMap) -> Vec<(u32, Vec<u8>)> {
+        memory.segments().map(|(s, d)| (s, d.to_vec())).collect()
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut memory = MemoryMap::new();
+        assert!(memory.is_empty());
+
+        memory.insert(10, &[1, 2, 3]);
+        memory.insert(20, &[4]);
+        memory.insert(0, &[]);
+        assert_eq!(segments(&memory), [(10, vec![1, 2, 3]), (20, vec![4])]);
+
+        // adjacent before and after
+        memory.insert(8, &[5, 6]);
+        memory.insert(21, &[7]);
+        assert_eq!(
+            segments(&memory),
+            [(8, vec![5, 6, 1, 2, 3]), (20, vec![4, 7])]
+        );
+
+        // overlapping and joining both segments
+        memory.insert(12, &[0xa; 9]);
+        assert_eq!(
+            segments(&memory),
+            [(
+                8,
+                vec![5, 6, 1, 2, 0xa, 0xa, 0xa, 0xa, 0xa, 0xa, 0xa, 0xa, 0xa, 7]
+            )]
+        );
+        assert_eq!(memory.len(), 14);
+
+        // inside an existing segment
+        memory.insert(9, &[0xb]);
+        assert_eq!(memory.get(9), Some(0xb));
+        assert_eq!(memory.get(7), None);
+        assert_eq!(memory.get(22), None);
+        assert_eq!(memory.segments().count(), 1);
+    }
+
+    #[test]
+    fn test_address_space() {
+        let mut memory = MemoryMap::new();
+        memory.insert(u32::MAX, &[1]);
+        memory.insert(u32::MAX - 1, &[2]);
+        assert_eq!(segments(&memory), [(u32::MAX - 1, vec![2, 1])]);
+        assert_eq!(memory.get(u32::MAX), Some(1));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_overflow() {
+        MemoryMap::new().insert(u32::MAX, &[1, 2]);
+    }
+
+    #[test]
+    fn test_to_vec() {
+        let mut memory = MemoryMap::new();
+        assert!(memory.to_vec(0xff).is_empty());
+        memory.insert(0x10, &[1]);
+        memory.insert(0x13, &[2, 3]);
+        assert_eq!(memory.to_vec(0xff), [1, 0xff, 0xff, 2, 3]);
+    }
+}
@@ -0,0 +1,98 @@
+use alloc::vec::Vec;
+
+use ::parity_scale_codec::{
+    Decode, DecodeWithMemTracking, Encode, EncodeLike, Error, Input, MaxEncodedLen, Output,
+};
+
+use crate::{Hex, UpperHex};
+
+macro_rules! impl_scale {
+    ($Hex:ident) => {
+        impl<T> Encode for $Hex<T>
+        where
+            T: Encode + ?Sized,
+        {
+            fn size_hint(&self) -> usize {
+                self.0.size_hint()
+            }
+
+            fn encode_to<O: Output + ?Sized>(&self, dest: &mut O) {
+                self.0.encode_to(dest)
+            }
+
+            fn encode(&self) -> Vec<u8> {
+                self.0.encode()
+            }
+
+            fn using_encoded<R, F: FnOnce(&[u8]) -> R>(&self, f: F) -> R {
+                self.0.using_encoded(f)
+            }
+
+            fn encoded_size(&self) -> usize {
+                self.0.encoded_size()
+            }
+        }
+
+        impl<T> EncodeLike for $Hex<T> where T: Encode {}
+
+        impl<T> Decode for $Hex<T>
+        where
+            T: Decode,
+        {
+            fn decode<I: Input>(input: &mut I) -> Result<Self, Error> {
+                T::decode(input).map($Hex)
+            }
+
+            fn skip<I: Input>(input: &mut I) -> Result<(), Error> {
+                T::skip(input)
+            }
+
+            fn encoded_fixed_size() -> Option<usize> {
+                T::encoded_fixed_size()
+            }
+        }
+
+        impl<T> DecodeWithMemTracking for $Hex<T> where T: DecodeWithMemTracking {}
+
+        impl<T> MaxEncodedLen for $Hex<T>
+        where
+            T: MaxEncodedLen,
+        {
+            fn max_encoded_len() -> usize {
+                T::max_encoded_len()
+            }
+        }
+    };
+}
+
+impl_scale!(Hex);
+impl_scale!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Encode, Decode, MaxEncodedLen, Debug, PartialEq)]
+    struct Transfer {
+        to: Hex<[u8; 4]>,
+        amount: UpperHex<[u8; 2]>,
+    }
+
+    #[test]
+    fn test_transparent() {
+        let transfer = Transfer {
+            to: Hex([1, 0x99, 0xff, 0]),
+            amount: UpperHex([0xab, 0xcd]),
+        };
+        let bytes = transfer.encode();
+        assert_eq!(bytes, [1, 0x99, 0xff, 0, 0xab, 0xcd]);
+        assert_eq!(Transfer::decode(&mut &bytes[..]).unwrap(), transfer);
+        assert_eq!(Transfer::max_encoded_len(), 6);
+
+        let hex = Hex(vec![0xab_u8, 0xcd]);
+        assert_eq!(hex.encode(), vec![0xab_u8, 0xcd].encode());
+        assert_eq!(hex.encoded_size(), 3);
+        assert_eq!(Hex::<Vec<u8>>::decode(&mut &hex.encode()[..]).unwrap(), hex);
+        assert!(Hex::<[u8; 4]>::decode(&mut &[1_u8, 2, 3][..]).is_err());
+    }
+}
@@ -0,0 +1,392 @@
+use alloc::vec::Vec;
+use core::{error::Error, fmt};
+
+use crate::{decode::fast_deserialize_into, FromHexError};
+
+/// Maximum number of bytes that gaps and repeated lines may fill in, in
+/// total. This keeps crafted offsets from forcing huge allocations.
+const MAX_FILL_LEN: u64 = 64 << 20;
+
+/// Textual hex dump formats understood by [`parse_dump`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DumpFormat {
+    /// Output of `xxd`, like `00000000: 6865 6c6c 6f0a  hello.`.
+    ///
+    /// Offsets are hexadecimal, bytes may be grouped arbitrarily.
+    /// This is also the output of `DumpWriter`.
+    Xxd,
+    /// Output of `hexdump -C`, like `00000000  68 65 6c 6c 6f 0a  |hello.|`.
+    ///
+    /// Offsets are hexadecimal.
+    HexdumpC,
+    /// Output of `od -x`, like `0000000 6568 6c6c 0a6f`.
+    ///
+    /// Offsets are octal, and bytes are grouped into little endian
+    /// 16 bit words.
+    OdX,
+}
+
+/// Error returned by [`parse_dump`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseDumpError {
+    /// The format of the dump could not be detected.
+    UnknownFormat,
+    /// A line does not match the dump format.
+    InvalidLine { line: usize },
+    /// A line contains invalid hex data.
+    InvalidHex { line: usize, error: FromHexError },
+    /// The offset of a line overlaps the data before it.
+    InvalidOffset {
+        line: usize,
+        expected: u64,
+        found: u64,
+    },
+    /// The offset of a line leaves a gap that is too large to fill in.
+    GapTooLarge { line: usize, offset: u64 },
+}
+
+impl fmt::Display for ParseDumpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownFormat => write!(f, "Unknown hex dump format"),
+            Self::InvalidLine { line } => write!(f, "Invalid hex dump line {line}"),
+            Self::InvalidHex { line, error } => write!(f, "{error} in hex dump line {line}"),
+            Self::InvalidOffset {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Invalid offset {found:#x} in hex dump line {line}, expected at least {expected:#x}"
+            ),
+            Self::GapTooLarge { line, offset } => write!(
+                f,
+                "Offset {offset:#x} in hex dump line {line} leaves a gap that is too large"
+            ),
+        }
+    }
+}
+
+impl Error for ParseDumpError {}
+
+/// Parses a textual hex dump back into bytes, like `xxd -r`.
+///
+/// The format is detected from the first line, see [`DumpFormat`].
+/// The ASCII column is ignored. Offsets are validated, and gaps between
+/// lines are filled with zeros. Lines consisting of a single `*` repeat the
+/// previous line up to the offset of the next line. The bytes filled in
+/// this way are limited to 64 MiB in total, larger gaps are reported as
+/// [`ParseDumpError::GapTooLarge`].
+///
+/// ```rust
+/// let dump = "\
+/// 00000000: 6865 6c6c 6f20 776f 726c 640a            hello world.
+/// ";
+/// assert_eq!(easy_hex::parse_dump(dump).unwrap(), b"hello world\n");
+/// ```
+pub fn parse_dump(input: &str) -> Result<Vec<u8>, ParseDumpError> {
+    let format = detect_format(input).ok_or(ParseDumpError::UnknownFormat)?;
+    parse_dump_as(input, format)
+}
+
+/// Parses a textual hex dump of the given format back into bytes.
+///
+/// See [`parse_dump`] for details.
+pub fn parse_dump_as(input: &str, format: DumpFormat) -> Result<Vec<u8>, ParseDumpError> {
+    let mut out = Vec::new();
+    // start of the last line, and whether to repeat it
+    let mut last_line = 0..0;
+    let mut repeat = false;
+    let mut filled = 0_u64;
+
+    for (i, text) in input.lines().enumerate() {
+        let line = i + 1;
+        let text = text.trim_end();
+        if text.is_empty() {
+            continue;
+        }
+        if text == "*" {
+            repeat = true;
+            continue;
+        }
+
+        let (offset, data) =
+            split_line(text, format).ok_or(ParseDumpError::InvalidLine { line })?;
+        let bytes = parse_data(data, format).map_err(|error| match error {
+            None => ParseDumpError::InvalidLine { line },
+            Some(error) => ParseDumpError::InvalidHex { line, error },
+        })?;
+
+        let len = out.len() as u64;
+        if offset < len {
+            // `od` pads the last line to full words, and reports
+            // the true length with a final offset line
+            if bytes.is_empty() && offset >= last_line.start as u64 {
+                out.truncate(offset as usize);
+                continue;
+            }
+            return Err(ParseDumpError::InvalidOffset {
+                line,
+                expected: len,
+                found: offset,
+            });
+        }
+
+        let too_large = ParseDumpError::GapTooLarge { line, offset };
+        filled = filled
+            .checked_add(offset - len)
+            .filter(|&filled| filled <= MAX_FILL_LEN)
+            .ok_or(too_large.clone())?;
+        // NB: the gap fits into `usize`, as it is bounded by `MAX_FILL_LEN`
+        let gap = (offset - len) as usize;
+        out.try_reserve(gap + bytes.len()).map_err(|_| too_large)?;
+        if repeat && !last_line.is_empty() {
+            let pattern = out[last_line.clone()].to_vec();
+            out.extend(pattern.iter().cycle().take(gap));
+        } else {
+            out.resize(out.len() + gap, 0);
+        }
+        repeat = false;
+
+        last_line = out.len()..out.len() + bytes.len();
+        out.extend_from_slice(&bytes);
+    }
+
+    Ok(out)
+}
+
+fn detect_format(input: &str) -> Option<DumpFormat> {
+    let text = input.lines().map(str::trim_end).find(|l| !l.is_empty())?;
+    let rest = text.trim_start_matches(|c: char| c.is_ascii_hexdigit());
+    if rest.len() == text.len() {
+        return None;
+    }
+    if rest.starts_with(':') {
+        Some(DumpFormat::Xxd)
+    } else if rest.starts_with("  ") {
+        Some(DumpFormat::HexdumpC)
+    } else if rest.is_empty() || rest.starts_with(' ') {
+        Some(DumpFormat::OdX)
+    } else {
+        None
+    }
+}
+
+/// Splits a line into its offset and its data column.
+fn split_line(text: &str, format: DumpFormat) -> Option<(u64, &str)> {
+    let (offset, rest, radix) = match format {
+        DumpFormat::Xxd => {
+            let (offset, rest) = text.split_once(':').unwrap_or((text, ""));
+            // the data column ends at the first double space
+            let rest = rest.strip_prefix(' ').unwrap_or(rest);
+            let rest = rest.split_once("  ").map_or(rest, |(data, _)| data);
+            (offset, rest, 16)
+        }
+        DumpFormat::HexdumpC => {
+            let (offset, rest) = text.split_once(' ').unwrap_or((text, ""));
+            let rest = rest.split_once('|').map_or(rest, |(data, _)| data);
+            (offset, rest, 16)
+        }
+        DumpFormat::OdX => {
+            let (offset, rest) = text.split_once(' ').unwrap_or((text, ""));
+            (offset, rest, 8)
+        }
+    };
+    if offset.is_empty() || !offset.bytes().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((u64::from_str_radix(offset, radix).ok()?, rest))
+}
+
+/// Decodes the data column of a line. Returns `None` as error if the
+/// grouping of the hex digits is invalid.
+fn parse_data(data: &str, format: DumpFormat) -> Result<Vec<u8>, Option<FromHexError>> {
+    let mut out = Vec::new();
+    for group in data.split_ascii_whitespace() {
+        match format {
+            DumpFormat::Xxd => {
+                let bytes: Vec<u8> = fast_deserialize_into(group).map_err(Some)?;
+                out.extend_from_slice(&bytes);
+            }
+            DumpFormat::HexdumpC => {
+                if group.len() != 2 {
+                    return Err(None);
+                }
+                let [byte]: [u8; 1] = fast_deserialize_into(group).map_err(Some)?;
+                out.push(byte);
+            }
+            DumpFormat::OdX => {
+                if group.len() != 4 {
+                    return Err(None);
+                }
+                let [high, low]: [u8; 2] = fast_deserialize_into(group).map_err(Some)?;
+                out.extend_from_slice(&[low, high]);
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] = b"hello world, this is a test\n";
+
+    #[test]
+    fn test_xxd() {
+        let dump = "\
+00000000: 6865 6c6c 6f20 776f 726c 642c 2074 6869  hello world, thi
+00000010: 7320 6973 2061 2074 6573 740a            s is a test.
+";
+        assert_eq!(parse_dump(dump).unwrap(), DATA);
+        assert_eq!(parse_dump_as(dump, DumpFormat::Xxd).unwrap(), DATA);
+
+        // grouping with `xxd -g1`, and an ASCII column that looks like hex
+        let dump = "00000000: de ad be ef  dead beef\n";
+        assert_eq!(parse_dump(dump).unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+    }
+
+    #[test]
+    fn test_hexdump() {
+        let dump = "\
+00000000  68 65 6c 6c 6f 20 77 6f  72 6c 64 2c 20 74 68 69  |hello world, thi|
+00000010  73 20 69 73 20 61 20 74  65 73 74 0a              |s is a test.|
+0000001c
+";
+        assert_eq!(parse_dump(dump).unwrap(), DATA);
+        assert_eq!(parse_dump_as(dump, DumpFormat::HexdumpC).unwrap(), DATA);
+    }
+
+    #[test]
+    fn test_od() {
+        let dump = "\
+0000000 6568 6c6c 206f 6f77 6c72 2c64 7420 6968
+0000020 2073 7369 6120 7420 7365 0a74
+0000034
+";
+        assert_eq!(parse_dump(dump).unwrap(), DATA);
+
+        // odd length, the last word is padded
+        let dump = "\
+0000000 6568 6c6c 006f
+0000005
+";
+        assert_eq!(parse_dump_as(dump, DumpFormat::OdX).unwrap(), b"hello");
+    }
+
+    #[test]
+    fn test_repeat() {
+        let dump = "\
+00000000  01 02 01 02 01 02 01 02  01 02 01 02 01 02 01 02  |................|
+*
+00000030  ff                                                |.|
+00000031
+";
+        let mut expected = [1, 2].repeat(24);
+        expected.push(0xff);
+        assert_eq!(parse_dump(dump).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_gap() {
+        let dump = "\
+00000000: 0102  ..
+00000010: ff  .
+";
+        let mut expected = vec![0; 17];
+        expected[..2].copy_from_slice(&[1, 2]);
+        expected[16] = 0xff;
+        assert_eq!(parse_dump(dump).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_huge_offset() {
+        for dump in ["ffffffffffffffff: 00\n", "7fffffff: 00\n"] {
+            let offset = u64::from_str_radix(&dump[..dump.find(':').unwrap()], 16).unwrap();
+            assert_eq!(
+                parse_dump(dump),
+                Err(ParseDumpError::GapTooLarge { line: 1, offset })
+            );
+        }
+
+        // the limit applies to all gaps together
+        let dump = "00000000: 01\n03000000: 02\n06000000: 03\n";
+        assert_eq!(
+            parse_dump(dump),
+            Err(ParseDumpError::GapTooLarge {
+                line: 3,
+                offset: 0x0600_0000
+            })
+        );
+
+        let dump = "00000000  01 02  |..|\n*\nffffffff  03  |.|\n";
+        assert_eq!(
+            parse_dump(dump),
+            Err(ParseDumpError::GapTooLarge {
+                line: 3,
+                offset: 0xffff_ffff
+            })
+        );
+        assert_eq!(
+            ParseDumpError::GapTooLarge {
+                line: 3,
+                offset: 0xffff_ffff
+            }
+            .to_string(),
+            "Offset 0xffffffff in hex dump line 3 leaves a gap that is too large"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_dump_writer_roundtrip() {
+        use std::io::Write;
+
+        use crate::DumpWriter;
+
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let mut dump = DumpWriter::new(Vec::new());
+        dump.write_all(&data).unwrap();
+        let dump = String::from_utf8(dump.finish().unwrap()).unwrap();
+        assert_eq!(parse_dump(&dump).unwrap(), data);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(parse_dump(""), Err(ParseDumpError::UnknownFormat));
+        assert_eq!(parse_dump("hello"), Err(ParseDumpError::UnknownFormat));
+        assert_eq!(
+            parse_dump("00000000: 0102\n00000001: 03\n"),
+            Err(ParseDumpError::InvalidOffset {
+                line: 2,
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            parse_dump("00000000: 0102\nxyz: 03\n"),
+            Err(ParseDumpError::InvalidLine { line: 2 })
+        );
+        assert_eq!(
+            parse_dump("00000000: 01zz\n"),
+            Err(ParseDumpError::InvalidHex {
+                line: 1,
+                error: FromHexError::InvalidHexCharacter { c: 'z', index: 2 }
+            })
+        );
+        assert_eq!(
+            parse_dump("00000000  010 2  |..|\n"),
+            Err(ParseDumpError::InvalidLine { line: 1 })
+        );
+        assert_eq!(
+            ParseDumpError::InvalidOffset {
+                line: 2,
+                expected: 2,
+                found: 1
+            }
+            .to_string(),
+            "Invalid offset 0x1 in hex dump line 2, expected at least 0x2"
+        );
+    }
+}
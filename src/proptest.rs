@@ -0,0 +1,141 @@
+//! Strategies for property testing hex parsers with `proptest`.
+//!
+//! `Hex<T>` and `UpperHex<T>` implement `Arbitrary` if `T` does, so they can
+//! be generated with `any::<Hex<T>>()`. The strategies in this module
+//! generate hex strings instead.
+//!
+//! ```rust
+//! use easy_hex::proptest::{hex_string, invalid_hex_string};
+//! use proptest::prelude::*;
+//!
+//! proptest! {
+//!     fn decodes(s in hex_string(0..64)) {
+//!         prop_assert!(easy_hex::decode_into::<Vec<u8>>(&s).is_ok());
+//!     }
+//!
+//!     fn rejects(s in invalid_hex_string(0..64)) {
+//!         prop_assert!(easy_hex::decode_into::<Vec<u8>>(&s).is_err());
+//!     }
+//! }
+//! # decodes();
+//! # rejects();
+//! ```
+
+use std::fmt;
+
+use ::proptest::{
+    arbitrary::{any, any_with, Arbitrary},
+    collection::{vec, SizeRange},
+    prop_oneof,
+    strategy::{Just, Map, Strategy},
+};
+
+use crate::{
+    encode::{byte2hex, HEX_CHARS_LOWER, HEX_CHARS_UPPER},
+    Hex, UpperHex,
+};
+
+macro_rules! impl_proptest {
+    ($Hex:ident) => {
+        impl<T> Arbitrary for $Hex<T>
+        where
+            T: Arbitrary,
+            // NB: `Arbitrary` requires `Debug`, which is all the wrapper
+            // needs from `T` beyond `Arbitrary` itself.
+            Self: fmt::Debug,
+        {
+            type Parameters = T::Parameters;
+            type Strategy = Map<T::Strategy, fn(T) -> Self>;
+
+            fn arbitrary_with(args: Self::Parameters) -> Self::Strategy {
+                any_with::<T>(args).prop_map($Hex)
+            }
+        }
+    };
+}
+
+impl_proptest!(Hex);
+impl_proptest!(UpperHex);
+
+/// Generates byte vectors together with their hex encoding, in randomly
+/// mixed case.
+///
+/// `len` is the number of bytes.
+pub fn hex_bytes(len: impl Into<SizeRange>) -> impl Strategy<Value = (Vec<u8>, String)> {
+    vec(any::<u8>(), len)
+        .prop_flat_map(|bytes| {
+            let case = vec(any::<bool>(), bytes.len() * 2);
+            (Just(bytes), case)
+        })
+        .prop_map(|(bytes, case)| {
+            let mut s = String::with_capacity(bytes.len() * 2);
+            for (&byte, case) in bytes.iter().zip(case.chunks(2)) {
+                let (lower_high, lower_low) = byte2hex(byte, HEX_CHARS_LOWER);
+                let (upper_high, upper_low) = byte2hex(byte, HEX_CHARS_UPPER);
+                s.push(char::from(if case[0] { upper_high } else { lower_high }));
+                s.push(char::from(if case[1] { upper_low } else { lower_low }));
+            }
+            (bytes, s)
+        })
+}
+
+/// Generates valid hex strings in randomly mixed case.
+///
+/// `len` is the number of encoded bytes.
+pub fn hex_string(len: impl Into<SizeRange>) -> impl Strategy<Value = String> {
+    hex_bytes(len).prop_map(|(_, s)| s)
+}
+
+/// Generates strings that are not valid hex, because they have an odd
+/// length or contain a character that is not a hex digit.
+///
+/// `len` is the number of bytes of the valid hex string the invalid one is
+/// derived from.
+pub fn invalid_hex_string(len: impl Into<SizeRange>) -> impl Strategy<Value = String> {
+    let len = len.into();
+    let odd_length = (hex_string(len.clone()), 0..16_usize).prop_map(|(mut s, digit)| {
+        s.push(char::from(HEX_CHARS_LOWER[digit]));
+        s
+    });
+    let bad_char = (
+        hex_string(len),
+        any::<usize>(),
+        any::<char>().prop_filter("not a hex digit", |c| !c.is_ascii_hexdigit()),
+    )
+        .prop_map(|(s, index, c)| {
+            let mut chars: Vec<char> = s.chars().collect();
+            match chars.len() {
+                0 => chars.push(c),
+                n => chars[index % n] = c,
+            }
+            chars.into_iter().collect()
+        });
+    prop_oneof![odd_length, bad_char]
+}
+
+#[cfg(test)]
+mod tests {
+    use ::proptest::{prop_assert, prop_assert_eq, proptest};
+
+    use super::*;
+    use crate::decode_into;
+
+    proptest! {
+        #[test]
+        fn test_hex_bytes((bytes, s) in hex_bytes(0..32)) {
+            prop_assert_eq!(s.len(), bytes.len() * 2);
+            prop_assert_eq!(decode_into::<Vec<u8>>(&s).unwrap(), bytes);
+        }
+
+        #[test]
+        fn test_invalid_hex_string(s in invalid_hex_string(0..32)) {
+            prop_assert!(decode_into::<Vec<u8>>(&s).is_err());
+        }
+
+        #[test]
+        fn test_arbitrary(hex in any::<Hex<[u8; 4]>>(), upper in any::<UpperHex<Vec<u8>>>()) {
+            prop_assert_eq!(hex.to_string().parse::<Hex<[u8; 4]>>().unwrap(), hex);
+            prop_assert_eq!(upper.to_string(), upper.to_string().to_uppercase());
+        }
+    }
+}
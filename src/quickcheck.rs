@@ -0,0 +1,49 @@
+use quickcheck::{Arbitrary, Gen};
+
+use crate::{Hex, UpperHex};
+
+macro_rules! impl_quickcheck {
+    ($Hex:ident) => {
+        impl<T> Arbitrary for $Hex<T>
+        where
+            T: Arbitrary,
+        {
+            fn arbitrary(g: &mut Gen) -> Self {
+                $Hex(T::arbitrary(g))
+            }
+
+            fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+                Box::new(self.0.shrink().map($Hex))
+            }
+        }
+    };
+}
+
+impl_quickcheck!(Hex);
+impl_quickcheck!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        fn roundtrip(hex: Hex<Vec<u8>>) -> bool {
+            hex.to_string().parse::<Hex<Vec<u8>>>().ok() == Some(hex)
+        }
+        quickcheck(roundtrip as fn(Hex<Vec<u8>>) -> bool);
+
+        fn roundtrip_upper(hex: UpperHex<Vec<u8>>) -> bool {
+            hex.to_string().parse::<UpperHex<Vec<u8>>>().ok() == Some(hex)
+        }
+        quickcheck(roundtrip_upper as fn(UpperHex<Vec<u8>>) -> bool);
+    }
+
+    #[test]
+    fn test_shrink() {
+        let hex = Hex(vec![1_u8, 2]);
+        assert!(hex.shrink().all(|h| h.len() <= 2));
+    }
+}
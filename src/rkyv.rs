@@ -0,0 +1,116 @@
+use core::ops::Deref;
+
+use ::rkyv::{
+    bytecheck::CheckBytes, rancor::Fallible, Archive, Deserialize, Place, Portable, Serialize,
+};
+
+use crate::{Hex, UpperHex};
+
+macro_rules! impl_rkyv {
+    ($Hex:ident, $ArchivedHex:ident, $case:literal) => {
+        #[doc = concat!("Archived form of `", stringify!($Hex), "<T>`.")]
+        ///
+        /// This wraps the archived form of `T`, and is formatted as a
+        #[doc = concat!($case, " hex string, like `", stringify!($Hex), "<T>` itself.")]
+        /// This allows printing archived data without deserializing it.
+        #[derive(Copy, Clone, Default, PartialOrd, Ord, Hash, Eq, PartialEq)]
+        #[repr(transparent)]
+        pub struct $ArchivedHex<T: ?Sized>(pub T);
+
+        // SAFETY: the wrapper is `repr(transparent)` over `T`, which is
+        // portable.
+        unsafe impl<T> Portable for $ArchivedHex<T> where T: Portable + ?Sized {}
+
+        // SAFETY: the wrapper is `repr(transparent)` over `T`, so it is
+        // valid exactly if `T` is.
+        unsafe impl<T, C> CheckBytes<C> for $ArchivedHex<T>
+        where
+            T: CheckBytes<C>,
+            C: Fallible + ?Sized,
+        {
+            unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+                T::check_bytes(value.cast(), context)
+            }
+        }
+
+        impl<T> Deref for $ArchivedHex<T>
+        where
+            T: ?Sized,
+        {
+            type Target = T;
+
+            fn deref(&self) -> &Self::Target {
+                &self.0
+            }
+        }
+
+        impl<T> Archive for $Hex<T>
+        where
+            T: Archive,
+        {
+            type Archived = $ArchivedHex<T::Archived>;
+            type Resolver = T::Resolver;
+
+            fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+                // SAFETY: the archived wrapper is `repr(transparent)` over
+                // `T::Archived`.
+                let out = unsafe { out.cast_unchecked::<T::Archived>() };
+                self.0.resolve(resolver, out);
+            }
+        }
+
+        impl<T, S> Serialize<S> for $Hex<T>
+        where
+            T: Serialize<S>,
+            S: Fallible + ?Sized,
+        {
+            fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+                self.0.serialize(serializer)
+            }
+        }
+
+        impl<T, A, D> Deserialize<$Hex<T>, D> for $ArchivedHex<A>
+        where
+            A: Deserialize<T, D>,
+            D: Fallible + ?Sized,
+        {
+            fn deserialize(&self, deserializer: &mut D) -> Result<$Hex<T>, D::Error> {
+                self.0.deserialize(deserializer).map($Hex)
+            }
+        }
+    };
+}
+
+impl_rkyv!(Hex, ArchivedHex, "lowercase");
+impl_rkyv!(UpperHex, ArchivedUpperHex, "uppercase");
+
+#[cfg(test)]
+mod tests {
+    use ::rkyv::{access, deserialize, rancor::Error, to_bytes};
+
+    use super::*;
+
+    #[derive(Archive, Serialize, Deserialize, Debug, PartialEq)]
+    struct Block {
+        id: Hex<[u8; 4]>,
+        data: UpperHex<Vec<u8>>,
+    }
+
+    #[test]
+    fn test_archive() {
+        let block = Block {
+            id: Hex([1, 0x99, 0xff, 0]),
+            data: UpperHex(vec![0xab, 0xcd]),
+        };
+        let bytes = to_bytes::<Error>(&block).unwrap();
+
+        let archived = access::<ArchivedBlock, Error>(&bytes).unwrap();
+        assert_eq!(archived.id, ArchivedHex([1, 0x99, 0xff, 0]));
+        assert_eq!(archived.id.to_string(), "0199ff00");
+        assert_eq!(format!("{:X}", archived.id), "0199FF00");
+        assert_eq!(format!("{:?}", archived.data), "ABCD");
+        assert_eq!(archived.data.len(), 2);
+
+        assert_eq!(deserialize::<Block, Error>(archived).unwrap(), block);
+    }
+}
@@ -0,0 +1,181 @@
+//! Storage of hex wrappers in SQLite databases with `rusqlite`.
+//!
+//! `Hex<T>` and `UpperHex<T>` implement `ToSql` and `FromSql`. When reading,
+//! both `BLOB` values with the raw bytes and `TEXT` values with a hex string
+//! are accepted. When writing, the policy is chosen per value: the wrappers
+//! themselves are written as `BLOB`, and wrapping them in [`Text`] writes
+//! them as hex string in their letter case instead.
+//!
+//! ```rust
+//! use easy_hex::{rusqlite::Text, Hex, UpperHex};
+//! use rusqlite::Connection;
+//!
+//! let conn = Connection::open_in_memory().unwrap();
+//! conn.execute("CREATE TABLE keys (raw BLOB, text TEXT)", ()).unwrap();
+//! conn.execute(
+//!     "INSERT INTO keys VALUES (?1, ?2)",
+//!     (Hex([1_u8, 0x99]), Text(UpperHex([0xab_u8, 0xcd]))),
+//! )
+//! .unwrap();
+//!
+//! let (raw, text): (Hex<[u8; 2]>, String) = conn
+//!     .query_row("SELECT raw, text FROM keys", (), |row| Ok((row.get(0)?, row.get(1)?)))
+//!     .unwrap();
+//! assert_eq!(raw, Hex([1, 0x99]));
+//! assert_eq!(text, "ABCD");
+//! ```
+
+use std::fmt::Display;
+
+use ::rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+use crate::{decode_into, from_hex_bytes::from_slice, FromHexBytes, Hex, UpperHex};
+
+/// Write policy that stores a value as hex string in a `TEXT` column.
+///
+/// The hex string uses the letter case of the wrapped value, so
+/// `Text(Hex(..))` writes lowercase and `Text(UpperHex(..))` writes
+/// uppercase hex. Reading accepts the same values as the wrapped type.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Text<H>(pub H);
+
+impl<H> ToSql for Text<H>
+where
+    H: Display,
+{
+    fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.0.to_string()))
+    }
+}
+
+impl<H> FromSql for Text<H>
+where
+    H: FromSql,
+{
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        H::column_result(value).map(Text)
+    }
+}
+
+fn from_value<T: FromHexBytes>(value: ValueRef<'_>) -> FromSqlResult<T> {
+    match value {
+        ValueRef::Blob(bytes) => from_slice(bytes).ok_or_else(|| match T::expected_len() {
+            Some(expected_size) => FromSqlError::InvalidBlobSize {
+                expected_size,
+                blob_size: bytes.len(),
+            },
+            None => {
+                FromSqlError::Other(format!("unsupported length of {} bytes", bytes.len()).into())
+            }
+        }),
+        ValueRef::Text(text) => {
+            let s = std::str::from_utf8(text).map_err(|err| FromSqlError::Other(err.into()))?;
+            decode_into(s).map_err(|err| FromSqlError::Other(err.into()))
+        }
+        _ => Err(FromSqlError::InvalidType),
+    }
+}
+
+macro_rules! impl_rusqlite {
+    ($Hex:ident) => {
+        impl<T> ToSql for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn to_sql(&self) -> ::rusqlite::Result<ToSqlOutput<'_>> {
+                Ok(ToSqlOutput::from(self.0.as_ref()))
+            }
+        }
+
+        impl<T> FromSql for $Hex<T>
+        where
+            T: FromHexBytes,
+        {
+            fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+                from_value(value).map($Hex)
+            }
+        }
+    };
+}
+
+impl_rusqlite!(Hex);
+impl_rusqlite!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use ::rusqlite::{types::Type, Connection, Error};
+
+    use super::*;
+
+    fn connect() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute("CREATE TABLE keys (id INTEGER PRIMARY KEY, value)", ())
+            .unwrap();
+        conn
+    }
+
+    fn insert(conn: &Connection, value: impl ToSql) {
+        conn.execute("INSERT INTO keys (value) VALUES (?1)", [value])
+            .unwrap();
+    }
+
+    fn select<T: FromSql>(conn: &Connection) -> Vec<::rusqlite::Result<T>> {
+        let mut stmt = conn.prepare("SELECT value FROM keys ORDER BY id").unwrap();
+        let rows = stmt.query_map((), |row| Ok(row.get(0))).unwrap();
+        rows.map(Result::unwrap).collect()
+    }
+
+    #[test]
+    fn test_write_policy() {
+        let conn = connect();
+        insert(&conn, Hex([1_u8, 0x99, 0xff]));
+        insert(&conn, Text(Hex(&[1_u8, 0x99, 0xff][..])));
+        insert(&conn, Text(UpperHex(vec![1_u8, 0x99, 0xff])));
+
+        let types = select::<String>(&conn);
+        assert!(matches!(
+            types[0],
+            Err(Error::InvalidColumnType(0, _, Type::Blob))
+        ));
+        assert_eq!(types[1].as_ref().unwrap(), "0199ff");
+        assert_eq!(types[2].as_ref().unwrap(), "0199FF");
+    }
+
+    #[test]
+    fn test_read_blob_and_text() {
+        let conn = connect();
+        insert(&conn, Hex([1_u8, 0x99, 0xff]));
+        insert(&conn, "0199Ff");
+
+        for value in select::<Hex<[u8; 3]>>(&conn) {
+            assert_eq!(value.unwrap(), Hex([1, 0x99, 0xff]));
+        }
+        for value in select::<Text<UpperHex<Vec<u8>>>>(&conn) {
+            assert_eq!(value.unwrap(), Text(UpperHex(vec![1, 0x99, 0xff])));
+        }
+    }
+
+    #[test]
+    fn test_read_invalid() {
+        let conn = connect();
+        insert(&conn, Hex([1_u8, 0x99]));
+        insert(&conn, "0199zz");
+        insert(&conn, 7);
+
+        let values = select::<Hex<[u8; 3]>>(&conn);
+        assert!(matches!(
+            values[0],
+            Err(Error::FromSqlConversionFailure(0, Type::Blob, ref err))
+                if err.to_string() == "Cannot read 3 byte value out of 2 byte blob"
+        ));
+        assert!(matches!(
+            values[1],
+            Err(Error::FromSqlConversionFailure(0, Type::Text, ref err))
+                if err.to_string() == "Invalid character 'z' at position 4"
+        ));
+        assert!(matches!(
+            values[2],
+            Err(Error::InvalidColumnType(0, _, Type::Integer))
+        ));
+    }
+}
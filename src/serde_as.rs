@@ -0,0 +1,155 @@
+use core::marker::PhantomData;
+
+use serde::{Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
+
+use crate::{
+    deserialize::deserialize, serialize::serialize, serialize::serialize_upper, FromHexBytes,
+};
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Letter case of a hex string, used as a parameter of [`As`].
+///
+/// This trait is sealed, and implemented by [`Lower`] and [`Upper`].
+pub trait Case: private::Sealed {
+    #[doc(hidden)]
+    const UPPER: bool;
+}
+
+/// Lowercase hex strings, see [`As`].
+pub struct Lower;
+
+/// Uppercase hex strings, see [`As`].
+pub struct Upper;
+
+impl private::Sealed for Lower {}
+impl Case for Lower {
+    const UPPER: bool = crate::LOWER;
+}
+
+impl private::Sealed for Upper {}
+impl Case for Upper {
+    const UPPER: bool = crate::UPPER;
+}
+
+/// Adapter for the `serde_with` crate.
+///
+/// Serializes any `T: AsRef<[u8]>` as a hex string in the letter case `C`,
+/// and deserializes any `T: FromHexBytes` from a hex string of either case.
+/// Can be used with `#[serde_as(as = "...")]`, and composes with other adapters:
+///
+/// ```rust
+/// use easy_hex::{As, Upper};
+/// use serde_derive::{Deserialize, Serialize};
+/// use serde_with::serde_as;
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize)]
+/// struct Example {
+///     #[serde_as(as = "Vec<As>")]
+///     lower: Vec<[u8; 4]>,
+///     #[serde_as(as = "Option<As<Upper>>")]
+///     upper: Option<Vec<u8>>,
+/// }
+/// ```
+pub struct As<C: Case = Lower>(PhantomData<C>);
+
+impl<T, C> SerializeAs<T> for As<C>
+where
+    T: AsRef<[u8]> + ?Sized,
+    C: Case,
+{
+    fn serialize_as<S>(source: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        if C::UPPER {
+            serialize_upper(source, serializer)
+        } else {
+            serialize(source, serializer)
+        }
+    }
+}
+
+impl<'de, T, C> DeserializeAs<'de, T> for As<C>
+where
+    T: FromHexBytes,
+    C: Case,
+{
+    fn deserialize_as<D>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde_derive::{Deserialize, Serialize};
+    use serde_with::serde_as;
+
+    use super::*;
+
+    #[serde_as]
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Test {
+        #[serde_as(as = "As")]
+        a: [u8; 2],
+        #[serde_as(as = "As<Upper>")]
+        b: Vec<u8>,
+        #[serde_as(as = "Vec<As<Lower>>")]
+        c: Vec<Vec<u8>>,
+        #[serde_as(as = "Option<As<Upper>>")]
+        d: Option<[u8; 1]>,
+        #[serde_as(as = "BTreeMap<As, As<Upper>>")]
+        e: BTreeMap<[u8; 1], Vec<u8>>,
+    }
+
+    const JSON: &str = r#"{"a":"0199","b":"ABFF","c":["01","","ff"],"d":null,"e":{"0a":"0B0C"}}"#;
+
+    fn test_value() -> Test {
+        Test {
+            a: [1, 0x99],
+            b: vec![0xab, 0xff],
+            c: vec![vec![1], vec![], vec![0xff]],
+            d: None,
+            e: [([0xa], vec![0xb, 0xc])].into_iter().collect(),
+        }
+    }
+
+    #[test]
+    fn test_serialize() {
+        assert_eq!(serde_json::to_string(&test_value()).unwrap(), JSON);
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let value: Test = serde_json::from_str(JSON).unwrap();
+        assert_eq!(value, test_value());
+
+        let json = r#"{"a":"0199","b":"abff","c":["01","","FF"],"d":"Aa","e":{"0A":"0b0c"}}"#;
+        let value: Test = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            value,
+            Test {
+                d: Some([0xaa]),
+                ..test_value()
+            }
+        );
+    }
+
+    #[test]
+    fn test_deserialize_errors() {
+        let json = r#"{"a":"019","b":"","c":[],"d":null,"e":{}}"#;
+        assert!(serde_json::from_str::<Test>(json).is_err());
+
+        let json = r#"{"a":"0199","b":"","c":["x"],"d":null,"e":{}}"#;
+        assert!(serde_json::from_str::<Test>(json).is_err());
+    }
+}
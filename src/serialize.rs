@@ -1,6 +1,6 @@
 use serde::{Serialize, Serializer};
 
-use crate::{encode::fast_serialize, Hex, UpperHex, LOWER, UPPER};
+use crate::{encode::fast_serialize, Hex, LenientHex, UpperHex, LOWER, UPPER};
 
 impl<T> Serialize for Hex<T>
 where
@@ -26,12 +26,24 @@ where
     }
 }
 
+impl<T> Serialize for LenientHex<T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        fast_serialize::<_, _, LOWER>(&self.0, |s| serializer.serialize_str(s))
+    }
+}
+
 /// Serialize function for a hex string. Will serialize `T` as lower case
 /// hex.
 pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
-    T: AsRef<[u8]>,
+    T: AsRef<[u8]> + ?Sized,
 {
     fast_serialize::<_, _, LOWER>(value, |s| serializer.serialize_str(s))
 }
@@ -41,7 +53,7 @@ where
 pub fn serialize_upper<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
-    T: AsRef<[u8]>,
+    T: AsRef<[u8]> + ?Sized,
 {
     fast_serialize::<_, _, UPPER>(value, |s| serializer.serialize_str(s))
 }
@@ -77,4 +89,19 @@ mod tests {
 
         assert_eq!(to_json(&hex), r#"{"data":"0199FF"}"#);
     }
+
+    #[test]
+    fn test_derive() {
+        let test = Test {
+            _a: Hex(vec![1]),
+            _b: UpperHex(vec![0xab]),
+            _c: vec![0x99],
+            _d: vec![0xff],
+        };
+
+        assert_eq!(
+            serde_json::to_string(&test).unwrap(),
+            r#"{"_a":"01","_b":"AB","_c":"99","_d":"FF"}"#
+        );
+    }
 }
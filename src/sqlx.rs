@@ -0,0 +1,128 @@
+use sqlx::{
+    decode::Decode,
+    encode::{Encode, IsNull},
+    error::BoxDynError,
+    Database, Type,
+};
+
+use crate::{from_hex_bytes::from_slice, FromHexBytes, Hex, UpperHex};
+
+fn length_error<T: FromHexBytes>(len: usize) -> BoxDynError {
+    match T::expected_len() {
+        Some(n) => format!("expected {n} bytes, found {len} bytes").into(),
+        None => format!("unsupported length of {len} bytes").into(),
+    }
+}
+
+macro_rules! impl_sqlx {
+    ($Hex:ident) => {
+        /// Stored as raw bytes, like `[u8]`.
+        ///
+        /// Wrap the value in `sqlx::types::Text` to store it as hex string
+        /// in a text column instead.
+        impl<T, DB> Type<DB> for $Hex<T>
+        where
+            T: ?Sized,
+            DB: Database,
+            [u8]: Type<DB>,
+        {
+            fn type_info() -> DB::TypeInfo {
+                <[u8] as Type<DB>>::type_info()
+            }
+
+            fn compatible(ty: &DB::TypeInfo) -> bool {
+                <[u8] as Type<DB>>::compatible(ty)
+            }
+        }
+
+        impl<'q, T, DB> Encode<'q, DB> for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+            DB: Database,
+            Vec<u8>: Encode<'q, DB>,
+        {
+            fn encode_by_ref(
+                &self,
+                buf: &mut DB::ArgumentBuffer<'q>,
+            ) -> Result<IsNull, BoxDynError> {
+                self.0.as_ref().to_vec().encode(buf)
+            }
+
+            fn size_hint(&self) -> usize {
+                self.0.as_ref().len()
+            }
+        }
+
+        impl<'r, T, DB> Decode<'r, DB> for $Hex<T>
+        where
+            T: FromHexBytes,
+            DB: Database,
+            &'r [u8]: Decode<'r, DB>,
+        {
+            fn decode(value: DB::ValueRef<'r>) -> Result<Self, BoxDynError> {
+                let bytes = <&[u8] as Decode<DB>>::decode(value)?;
+                from_slice(bytes)
+                    .map($Hex)
+                    .ok_or_else(|| length_error::<T>(bytes.len()))
+            }
+        }
+    };
+}
+
+impl_sqlx!(Hex);
+impl_sqlx!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use sqlx::{types::Text, Connection, Row, SqliteConnection};
+
+    use super::*;
+
+    async fn connect() -> SqliteConnection {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::query("CREATE TABLE keys (id INTEGER PRIMARY KEY, raw BLOB, text TEXT)")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+        conn
+    }
+
+    #[tokio::test]
+    async fn test_round_trip() {
+        let mut conn = connect().await;
+        sqlx::query("INSERT INTO keys (raw, text) VALUES (?, ?)")
+            .bind(Hex([1_u8, 0x99, 0xff, 0]))
+            .bind(Text(UpperHex(vec![0xab_u8, 0xcd])))
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let row = sqlx::query("SELECT raw, text, hex(raw) FROM keys")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        assert_eq!(row.get::<Hex<[u8; 4]>, _>(0), Hex([1, 0x99, 0xff, 0]));
+        assert_eq!(row.get::<Vec<u8>, _>(0), [1, 0x99, 0xff, 0]);
+        assert_eq!(row.get::<String, _>(1), "ABCD");
+        assert_eq!(row.get::<Text<Hex<Vec<u8>>>, _>(1).0, Hex(vec![0xab, 0xcd]));
+        assert_eq!(row.get::<String, _>(2), "0199FF00");
+    }
+
+    #[tokio::test]
+    async fn test_invalid() {
+        let mut conn = connect().await;
+        sqlx::query("INSERT INTO keys (raw, text) VALUES (x'0199', 'zz')")
+            .execute(&mut conn)
+            .await
+            .unwrap();
+
+        let row = sqlx::query("SELECT raw, text FROM keys")
+            .fetch_one(&mut conn)
+            .await
+            .unwrap();
+        let err = row.try_get::<Hex<[u8; 4]>, _>(0).unwrap_err();
+        assert!(err.to_string().contains("expected 4 bytes, found 2 bytes"));
+        let err = row.try_get::<Text<Hex<Vec<u8>>>, _>(1).unwrap_err();
+        assert!(err.to_string().contains("Invalid character 'z'"));
+    }
+}
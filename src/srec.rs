@@ -0,0 +1,420 @@
+//! Motorola S-record firmware files.
+//!
+//! ```rust
+//! use easy_hex::srec::{AddressWidth, Srec};
+//!
+//! let image = Srec::parse("S107001001020304DE\nS9030000FC\n").unwrap();
+//! assert_eq!(image.memory.get(0x12), Some(3));
+//! assert_eq!(
+//!     image.encode(16, AddressWidth::Bits16),
+//!     "S107001001020304DE\nS5030001FB\nS9030000FC\n"
+//! );
+//! ```
+
+use alloc::{string::String, vec::Vec};
+use core::{error::Error, fmt, str::FromStr};
+
+use crate::{
+    decode::fast_deserialize_into, encode::fast_serialize, FromHexError, MemoryMap, UPPER,
+};
+
+/// Width of the addresses in data and termination records.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AddressWidth {
+    /// 16 bit addresses, using `S1` and `S9` records.
+    Bits16,
+    /// 24 bit addresses, using `S2` and `S8` records.
+    Bits24,
+    /// 32 bit addresses, using `S3` and `S7` records.
+    Bits32,
+}
+
+impl AddressWidth {
+    fn bytes(self) -> usize {
+        match self {
+            Self::Bits16 => 2,
+            Self::Bits24 => 3,
+            Self::Bits32 => 4,
+        }
+    }
+
+    fn fits(self, end: u64) -> bool {
+        end <= 1 << (8 * self.bytes())
+    }
+}
+
+/// Contents of a Motorola S-record file.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Srec {
+    /// The data of the `S0` header record, if any.
+    pub header: Option<Vec<u8>>,
+    /// The data records.
+    pub memory: MemoryMap,
+    /// The start address of the termination record, if any.
+    pub start: Option<u32>,
+}
+
+/// Error returned when parsing an S-record file.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// A record is malformed, or its length does not match its type.
+    InvalidRecord { line: usize },
+    /// A record contains invalid hex data.
+    InvalidHex { line: usize, error: FromHexError },
+    /// The checksum of a record does not match its contents.
+    InvalidChecksum {
+        line: usize,
+        expected: u8,
+        found: u8,
+    },
+    /// A record has an unknown type.
+    UnknownRecordType { line: usize, record_type: char },
+    /// A data record extends beyond the 32 bit address space.
+    AddressOverflow { line: usize },
+    /// A count record does not match the number of data records before it.
+    InvalidRecordCount {
+        line: usize,
+        expected: u32,
+        found: u32,
+    },
+    /// The file contains records after the termination record.
+    RecordAfterTermination { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidRecord { line } => write!(f, "Invalid record in line {line}"),
+            Self::InvalidHex { line, error } => write!(f, "{error} in line {line}"),
+            Self::InvalidChecksum {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Invalid checksum {found:02X} in line {line}, expected {expected:02X}"
+            ),
+            Self::UnknownRecordType { line, record_type } => {
+                write!(f, "Unknown record type S{record_type} in line {line}")
+            }
+            Self::AddressOverflow { line } => write!(f, "Address overflow in line {line}"),
+            Self::InvalidRecordCount {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "Invalid record count {found} in line {line}, expected {expected}"
+            ),
+            Self::RecordAfterTermination { line } => {
+                write!(f, "Record after termination record in line {line}")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+fn checksum(bytes: &[u8]) -> u8 {
+    !bytes.iter().fold(0u8, |sum, &b| sum.wrapping_add(b))
+}
+
+fn push_record(out: &mut String, record_type: char, address: u32, width: usize, data: &[u8]) {
+    debug_assert!(data.len() + width < 0xff);
+
+    let mut bytes = Vec::with_capacity(data.len() + width + 2);
+    bytes.push((data.len() + width + 1) as u8);
+    bytes.extend_from_slice(&address.to_be_bytes()[4 - width..]);
+    bytes.extend_from_slice(data);
+    bytes.push(checksum(&bytes));
+
+    out.push('S');
+    out.push(record_type);
+    fast_serialize::<_, _, UPPER>(&bytes, |hex| out.push_str(hex));
+    out.push('\n');
+}
+
+impl Srec {
+    /// Parses the records of an S-record file.
+    ///
+    /// Checksums and count records are validated. Data records of
+    /// different address widths may be mixed. Empty lines are ignored.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut image = Self::default();
+        let mut count = 0u32;
+        let mut terminated = false;
+
+        for (i, text) in input.lines().enumerate() {
+            let line = i + 1;
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            if terminated {
+                return Err(ParseError::RecordAfterTermination { line });
+            }
+
+            let mut chars = text.chars();
+            if chars.next() != Some('S') {
+                return Err(ParseError::InvalidRecord { line });
+            }
+            let record_type = chars.next().ok_or(ParseError::InvalidRecord { line })?;
+            let width = match record_type {
+                '0' | '1' | '5' | '9' => 2,
+                '2' | '6' | '8' => 3,
+                '3' | '7' => 4,
+                _ => return Err(ParseError::UnknownRecordType { line, record_type }),
+            };
+
+            let bytes: Vec<u8> = fast_deserialize_into(chars.as_str())
+                .map_err(|error| ParseError::InvalidHex { line, error })?;
+            if bytes.len() < width + 2 || bytes.len() != usize::from(bytes[0]) + 1 {
+                return Err(ParseError::InvalidRecord { line });
+            }
+
+            let (record, found) = bytes.split_at(bytes.len() - 1);
+            let (found, expected) = (found[0], checksum(record));
+            if found != expected {
+                return Err(ParseError::InvalidChecksum {
+                    line,
+                    expected,
+                    found,
+                });
+            }
+
+            let mut address = [0; 4];
+            address[4 - width..].copy_from_slice(&record[1..][..width]);
+            let address = u32::from_be_bytes(address);
+            let data = &record[1 + width..];
+
+            match record_type {
+                '0' => image.header = Some(data.to_vec()),
+                '1' | '2' | '3' => {
+                    if u64::from(address) + data.len() as u64 > 1 << 32 {
+                        return Err(ParseError::AddressOverflow { line });
+                    }
+                    image.memory.insert(address, data);
+                    count += 1;
+                }
+                _ if !data.is_empty() => return Err(ParseError::InvalidRecord { line }),
+                '5' | '6' => {
+                    if address != count {
+                        return Err(ParseError::InvalidRecordCount {
+                            line,
+                            expected: count,
+                            found: address,
+                        });
+                    }
+                }
+                _ => {
+                    image.start = Some(address);
+                    terminated = true;
+                }
+            }
+        }
+
+        Ok(image)
+    }
+
+    /// Returns the smallest address width that fits all data and the start
+    /// address.
+    pub fn address_width(&self) -> AddressWidth {
+        let end = self
+            .memory
+            .segments()
+            .map(|(s, d)| u64::from(s) + d.len() as u64)
+            .chain(self.start.map(|s| u64::from(s) + 1))
+            .max()
+            .unwrap_or(0);
+        [AddressWidth::Bits16, AddressWidth::Bits24]
+            .into_iter()
+            .find(|w| w.fits(end))
+            .unwrap_or(AddressWidth::Bits32)
+    }
+
+    /// Writes the memory map as S-records with up to `record_len` data bytes
+    /// each, using addresses of the given `width`.
+    ///
+    /// The header record is written if present, followed by the data
+    /// records, a count record if the count fits into 24 bits, and a
+    /// termination record if a start address is present.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `record_len` is `0`, if it is too long for a record with
+    /// addresses of the given width, or if the data or start address do not
+    /// fit into `width`.
+    pub fn encode(&self, record_len: u8, width: AddressWidth) -> String {
+        assert!(record_len > 0, "record length must not be 0");
+        assert!(
+            usize::from(record_len) + width.bytes() < 0xff,
+            "record length too long"
+        );
+        assert!(width >= self.address_width(), "address width too small");
+
+        let (data_type, start_type) = match width {
+            AddressWidth::Bits16 => ('1', '9'),
+            AddressWidth::Bits24 => ('2', '8'),
+            AddressWidth::Bits32 => ('3', '7'),
+        };
+
+        let mut out = String::new();
+        if let Some(header) = &self.header {
+            let header = &header[..header.len().min(0xff - 3)];
+            push_record(&mut out, '0', 0, 2, header);
+        }
+
+        let mut count = 0u32;
+        for (start, data) in self.memory.segments() {
+            for (i, chunk) in data.chunks(usize::from(record_len)).enumerate() {
+                let address = start + (i * usize::from(record_len)) as u32;
+                push_record(&mut out, data_type, address, width.bytes(), chunk);
+                count += 1;
+            }
+        }
+
+        if count <= 0xffff {
+            push_record(&mut out, '5', count, 2, &[]);
+        } else if count <= 0xff_ffff {
+            push_record(&mut out, '6', count, 3, &[]);
+        }
+        if let Some(start) = self.start {
+            push_record(&mut out, start_type, start, width.bytes(), &[]);
+        }
+        out
+    }
+}
+
+impl FromStr for Srec {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Writes records of 16 bytes with the smallest fitting address width,
+/// see [`Srec::encode`].
+impl fmt::Display for Srec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode(16, self.address_width()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let input = "\
+S00F000068656C6C6F202020202000003C
+S11F00007C0802A6900100049421FFF07C6C1B787C8C23783C6000003863000026
+S11F001C4BFFFFE5398000007D83637880010014382100107C0803A64E800020E9
+S111003848656C6C6F20776F726C642E0A0042
+S5030003F9
+S9030000FC
+";
+        let image: Srec = input.parse().unwrap();
+        assert_eq!(image.header.as_deref(), Some(&b"hello     \0\0"[..]));
+        assert_eq!(image.start, Some(0));
+        assert_eq!(image.memory.len(), 0x46);
+        assert_eq!(image.memory.get(0x38), Some(b'H'));
+        assert_eq!(image.address_width(), AddressWidth::Bits16);
+
+        assert_eq!(image.encode(0x1c, AddressWidth::Bits16), input);
+    }
+
+    #[test]
+    fn test_address_widths() {
+        let mut image = Srec {
+            start: Some(0x1234),
+            ..Default::default()
+        };
+        image.memory.insert(0xfffe, &[1, 2]);
+        assert_eq!(image.address_width(), AddressWidth::Bits16);
+        assert_eq!(
+            image.to_string(),
+            "S105FFFE0102FA\nS5030001FB\nS9031234B6\n"
+        );
+
+        image.memory.insert(0x10000, &[3]);
+        assert_eq!(image.address_width(), AddressWidth::Bits24);
+        assert_eq!(
+            image.to_string(),
+            "S20700FFFE010203F5\nS5030001FB\nS804001234B5\n"
+        );
+
+        let encoded = image.encode(2, AddressWidth::Bits32);
+        assert_eq!(
+            encoded,
+            "S3070000FFFE0102F8\nS3060001000003F5\nS5030002FA\nS70500001234B4\n"
+        );
+        assert_eq!(Srec::parse(&encoded).unwrap(), image);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_width_too_small() {
+        let mut image = Srec::default();
+        image.memory.insert(0x10000, &[1]);
+        image.encode(16, AddressWidth::Bits16);
+    }
+
+    #[test]
+    fn test_errors() {
+        let parse = |s: &str| Srec::parse(s).unwrap_err();
+
+        assert_eq!(parse(":030000FC"), ParseError::InvalidRecord { line: 1 });
+        assert_eq!(parse("S"), ParseError::InvalidRecord { line: 1 });
+        assert_eq!(parse("S10200FD"), ParseError::InvalidRecord { line: 1 });
+        assert_eq!(parse("S1040000FB"), ParseError::InvalidRecord { line: 1 });
+        assert_eq!(parse("S9040000FFFC"), ParseError::InvalidRecord { line: 1 });
+        assert_eq!(
+            parse("S4030000FC"),
+            ParseError::UnknownRecordType {
+                line: 1,
+                record_type: '4'
+            }
+        );
+        assert_eq!(
+            parse("S9030000F"),
+            ParseError::InvalidHex {
+                line: 1,
+                error: FromHexError::OddLength
+            }
+        );
+        assert_eq!(
+            parse("S9030000FD"),
+            ParseError::InvalidChecksum {
+                line: 1,
+                expected: 0xfc,
+                found: 0xfd
+            }
+        );
+        assert_eq!(
+            parse("S307FFFFFFFF0102F9"),
+            ParseError::AddressOverflow { line: 1 }
+        );
+        assert_eq!(
+            parse("S1040000FFFC\nS5030002FA"),
+            ParseError::InvalidRecordCount {
+                line: 2,
+                expected: 1,
+                found: 2
+            }
+        );
+        assert_eq!(
+            parse("S9030000FC\nS9030000FC"),
+            ParseError::RecordAfterTermination { line: 2 }
+        );
+        assert_eq!(
+            ParseError::UnknownRecordType {
+                line: 3,
+                record_type: '4'
+            }
+            .to_string(),
+            "Unknown record type S4 in line 3"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+use std::io::{self, Write};
+
+use crate::{
+    encode::{byte2hex, HEX_CHARS_LOWER, HEX_CHARS_UPPER},
+    FromHexError,
+};
+
+fn invalid_data(err: FromHexError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+/// Streaming hex encoder.
+///
+/// All bytes written to this adapter get encoded as hex and written to the
+/// inner writer. Each byte can be prefixed, bytes can be separated, and the
+/// output can be wrapped into lines of a fixed number of bytes.
+///
+/// ```rust
+/// use std::io::Write;
+/// use easy_hex::EncodeWriter;
+///
+/// let mut encoder = EncodeWriter::new(Vec::new())
+///     .prefix("0x")
+///     .separator(", ");
+/// encoder.write_all(&[1, 16, 255]).unwrap();
+/// let out = encoder.finish().unwrap();
+/// assert_eq!(out, b"0x01, 0x10, 0xff");
+/// ```
+pub struct EncodeWriter<W: Write> {
+    inner: W,
+    alpha: &'static [u8; 16],
+    prefix: String,
+    separator: String,
+    wrap: Option<usize>,
+    line_len: usize,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> EncodeWriter<W> {
+    /// Creates a new encoder that writes lowercase hex to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            alpha: HEX_CHARS_LOWER,
+            prefix: String::new(),
+            separator: String::new(),
+            wrap: None,
+            line_len: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Sets whether to write uppercase hex.
+    pub fn upper(mut self, upper: bool) -> Self {
+        self.alpha = if upper {
+            HEX_CHARS_UPPER
+        } else {
+            HEX_CHARS_LOWER
+        };
+        self
+    }
+
+    /// Sets a prefix that is written before every byte, like `0x`.
+    pub fn prefix(mut self, prefix: &str) -> Self {
+        self.prefix = prefix.to_owned();
+        self
+    }
+
+    /// Sets a separator that is written between bytes on the same line.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_owned();
+        self
+    }
+
+    /// Wraps the output into lines of `bytes` bytes each.
+    ///
+    /// A value of `0` disables wrapping.
+    pub fn wrap(mut self, bytes: usize) -> Self {
+        self.wrap = (bytes > 0).then_some(bytes);
+        self
+    }
+
+    /// Flushes the encoder and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+}
+
+impl<W: Write> Write for EncodeWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.clear();
+        for &byte in data {
+            if self.wrap == Some(self.line_len) {
+                self.buf.push(b'\n');
+                self.line_len = 0;
+            }
+            if self.line_len > 0 {
+                self.buf.extend_from_slice(self.separator.as_bytes());
+            }
+            self.buf.extend_from_slice(self.prefix.as_bytes());
+            let (high, low) = byte2hex(byte, self.alpha);
+            self.buf.extend_from_slice(&[high, low]);
+            self.line_len += 1;
+        }
+        self.inner.write_all(&self.buf)?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// Streaming hex decoder.
+///
+/// All hex text written to this adapter gets decoded, and the resulting
+/// bytes are written to the inner writer. This accepts both lower and
+/// upper case hex.
+///
+/// In lenient mode, ASCII whitespace, the separators `,`, `:`, `-` and `_`,
+/// and `0x` prefixes in front of a byte are skipped.
+///
+/// ```rust
+/// use std::io::Write;
+/// use easy_hex::DecodeWriter;
+///
+/// let mut decoder = DecodeWriter::new(Vec::new()).lenient(true);
+/// decoder.write_all(b"0x01, 0x10,\n0xFF").unwrap();
+/// let out = decoder.finish().unwrap();
+/// assert_eq!(out, [1, 16, 255]);
+/// ```
+pub struct DecodeWriter<W: Write> {
+    inner: W,
+    lenient: bool,
+    high: Option<u8>,
+    index: usize,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> DecodeWriter<W> {
+    /// Creates a new strict decoder that writes bytes to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            lenient: false,
+            high: None,
+            index: 0,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Sets whether to skip whitespace, separators and `0x` prefixes.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Checks that no incomplete byte is left, flushes the decoder,
+    /// and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.high.is_some() {
+            return Err(invalid_data(FromHexError::OddLength));
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    fn push(&mut self, c: u8) -> Result<(), FromHexError> {
+        let index = self.index;
+        self.index += 1;
+
+        let nibble = match c {
+            b'0'..=b'9' => c - b'0',
+            b'a'..=b'f' => c - b'a' + 10,
+            b'A'..=b'F' => c - b'A' + 10,
+            // `0x` prefix, the `0` has already been consumed as high nibble
+            b'x' | b'X' if self.lenient && self.high == Some(0) => {
+                self.high = None;
+                return Ok(());
+            }
+            b',' | b':' | b'-' | b'_' if self.lenient && self.high.is_none() => return Ok(()),
+            c if self.lenient && self.high.is_none() && c.is_ascii_whitespace() => return Ok(()),
+            c => {
+                let c = if c.is_ascii() {
+                    char::from(c)
+                } else {
+                    char::REPLACEMENT_CHARACTER
+                };
+                return Err(FromHexError::InvalidHexCharacter { c, index });
+            }
+        };
+
+        match self.high.take() {
+            Some(high) => self.buf.push(high << 4 | nibble),
+            None => self.high = Some(nibble),
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for DecodeWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.buf.clear();
+        for &c in data {
+            self.push(c).map_err(invalid_data)?;
+        }
+        self.inner.write_all(&self.buf)?;
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode(data: &[u8], encoder: EncodeWriter<Vec<u8>>) -> String {
+        let mut encoder = encoder;
+        // write in small pieces to exercise the state between writes
+        for chunk in data.chunks(3) {
+            encoder.write_all(chunk).unwrap();
+        }
+        String::from_utf8(encoder.finish().unwrap()).unwrap()
+    }
+
+    fn decode(data: &str, lenient: bool) -> io::Result<Vec<u8>> {
+        let mut decoder = DecodeWriter::new(Vec::new()).lenient(lenient);
+        for chunk in data.as_bytes().chunks(3) {
+            decoder.write_all(chunk)?;
+        }
+        decoder.finish()
+    }
+
+    #[test]
+    fn test_encode() {
+        let data = [1, 0x99, 0xff, 0, 0xab];
+        let new = || EncodeWriter::new(Vec::new());
+
+        assert_eq!(encode(&data, new()), "0199ff00ab");
+        assert_eq!(encode(&data, new().upper(true)), "0199FF00AB");
+        assert_eq!(encode(&data, new().separator(" ")), "01 99 ff 00 ab");
+        assert_eq!(
+            encode(&data, new().prefix("0x").separator(", ").upper(true)),
+            "0x01, 0x99, 0xFF, 0x00, 0xAB"
+        );
+        assert_eq!(encode(&data, new().wrap(2)), "0199\nff00\nab");
+        assert_eq!(
+            encode(&data, new().wrap(4).separator(":")),
+            "01:99:ff:00\nab"
+        );
+        assert_eq!(encode(&data, new().wrap(0)), "0199ff00ab");
+        assert_eq!(encode(&[], new().wrap(2)), "");
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("0199fF", false).unwrap(), [1, 0x99, 0xff]);
+        assert!(decode("", false).unwrap().is_empty());
+        assert_eq!(
+            decode("0x01, 0X99,\n  ff:00-ab_cd\r\n", true).unwrap(),
+            [1, 0x99, 0xff, 0, 0xab, 0xcd]
+        );
+        assert_eq!(
+            decode("00x00", true).unwrap_err().kind(),
+            io::ErrorKind::InvalidData
+        );
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let err = decode("0199f", false).unwrap_err();
+        assert_eq!(err.to_string(), FromHexError::OddLength.to_string());
+
+        let err = decode("01 99", false).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            FromHexError::InvalidHexCharacter { c: ' ', index: 2 }.to_string()
+        );
+
+        // separators are only allowed between bytes
+        let err = decode("0 1", true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            FromHexError::InvalidHexCharacter { c: ' ', index: 1 }.to_string()
+        );
+
+        let err = decode("01ä", true).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            FromHexError::InvalidHexCharacter {
+                c: char::REPLACEMENT_CHARACTER,
+                index: 2
+            }
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let data: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let hex = encode(&data, EncodeWriter::new(Vec::new()).wrap(16).separator(" "));
+        assert_eq!(decode(&hex, true).unwrap(), data);
+
+        let hex = encode(&data, EncodeWriter::new(Vec::new()));
+        assert_eq!(hex, crate::encode(&data, |s| s.to_owned()));
+        assert_eq!(decode(&hex, false).unwrap(), data);
+    }
+}
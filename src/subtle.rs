@@ -0,0 +1,180 @@
+use core::str::FromStr;
+
+use subtle::{Choice, ConstantTimeEq};
+
+use crate::{decode_into, CtHex, FromHexBytes, FromHexError, Hex, UpperHex};
+
+/// Compares a nibble with a hex digit of either case, without branching on
+/// the nibble or using it as table index.
+fn ct_eq_nibble(nibble: u8, c: u8) -> Choice {
+    let n = u16::from(nibble);
+    // 0x27 for nibbles above 9, which moves them from ':' to 'a'
+    let letter = (9_u16.wrapping_sub(n) >> 8) & 0x27;
+    let lower = (n + u16::from(b'0') + letter) as u8;
+    let upper = (n + u16::from(b'0') + (letter & 0x07)) as u8;
+    c.ct_eq(&lower) | c.ct_eq(&upper)
+}
+
+pub(crate) fn ct_eq_str(bytes: &[u8], s: &str) -> Choice {
+    let s = s.as_bytes();
+    if s.len() != bytes.len() * 2 {
+        return Choice::from(0);
+    }
+    bytes
+        .iter()
+        .zip(s.chunks_exact(2))
+        .fold(Choice::from(1), |eq, (byte, digits)| {
+            eq & ct_eq_nibble(byte >> 4, digits[0]) & ct_eq_nibble(byte & 0xf, digits[1])
+        })
+}
+
+macro_rules! impl_subtle {
+    ($Hex:ident) => {
+        impl<T> ConstantTimeEq for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn ct_eq(&self, other: &Self) -> Choice {
+                self.0.as_ref().ct_eq(other.0.as_ref())
+            }
+        }
+
+        impl<T> $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            /// Compares the bytes with a hex string of either case in
+            /// constant time, without decoding the string.
+            ///
+            /// Only the length of the string can be observed. Strings
+            /// that are not valid hex compare as not equal.
+            pub fn ct_eq_str(&self, s: &str) -> Choice {
+                ct_eq_str(self.0.as_ref(), s)
+            }
+        }
+    };
+}
+
+impl_subtle!(Hex);
+impl_subtle!(UpperHex);
+impl_subtle!(CtHex);
+
+impl<T> PartialEq for CtHex<T>
+where
+    T: AsRef<[u8]> + ?Sized,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<T> Eq for CtHex<T> where T: AsRef<[u8]> + ?Sized {}
+
+impl<T> FromStr for CtHex<T>
+where
+    T: FromHexBytes,
+{
+    type Err = FromHexError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        decode_into(s).map(CtHex)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use crate::{encode::fast_serialize, CtHex, FromHexBytes, Hex, LOWER};
+
+    impl<T> Serialize for CtHex<T>
+    where
+        T: AsRef<[u8]> + ?Sized,
+    {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            fast_serialize::<_, _, LOWER>(&self.0, |s| serializer.serialize_str(s))
+        }
+    }
+
+    impl<'a, T> Deserialize<'a> for CtHex<T>
+    where
+        T: FromHexBytes,
+    {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: Deserializer<'a>,
+        {
+            Hex::deserialize(deserializer).map(|hex: Hex<T>| CtHex(hex.0))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ct_eq_nibble() {
+        for nibble in 0..16 {
+            let lower = format!("{nibble:x}").as_bytes()[0];
+            let upper = format!("{nibble:X}").as_bytes()[0];
+            for c in 0..=255 {
+                let eq = bool::from(ct_eq_nibble(nibble, c));
+                assert_eq!(eq, c == lower || c == upper, "{nibble} {c}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_ct_eq() {
+        assert!(bool::from(Hex([1_u8, 0x99]).ct_eq(&Hex([1, 0x99]))));
+        assert!(!bool::from(Hex([1_u8, 0x99]).ct_eq(&Hex([1, 0x98]))));
+        assert!(!bool::from(
+            UpperHex(&[1_u8][..]).ct_eq(&UpperHex(&[1, 0][..]))
+        ));
+
+        assert_eq!(CtHex(vec![1_u8, 0x99]), CtHex(vec![1, 0x99]));
+        assert_ne!(CtHex(vec![1_u8, 0x99]), CtHex(vec![1, 0x98]));
+        assert_ne!(CtHex(vec![1_u8, 0x99]), CtHex(vec![1]));
+    }
+
+    #[test]
+    fn test_ct_eq_str() {
+        let hex = Hex([1_u8, 0x99, 0xff]);
+        assert!(bool::from(hex.ct_eq_str("0199ff")));
+        assert!(bool::from(hex.ct_eq_str("0199FF")));
+        assert!(bool::from(hex.ct_eq_str("0199Ff")));
+        assert!(!bool::from(hex.ct_eq_str("0199fe")));
+        assert!(!bool::from(hex.ct_eq_str("0199f")));
+        assert!(!bool::from(hex.ct_eq_str("0199ff00")));
+        assert!(!bool::from(hex.ct_eq_str("\u{10}199ff")));
+        assert!(!bool::from(hex.ct_eq_str("0199f\u{ff}")));
+
+        assert!(bool::from(UpperHex([0xab_u8]).ct_eq_str("ab")));
+        assert!(bool::from(CtHex(&[][..]).ct_eq_str("")));
+    }
+
+    #[test]
+    fn test_fmt_and_parse() {
+        let hex: CtHex<[u8; 3]> = "0199fF".parse().unwrap();
+        assert_eq!(hex, CtHex([1, 0x99, 0xff]));
+        assert_eq!(hex.to_string(), "0199ff");
+        assert_eq!(format!("{hex:X}"), "0199FF");
+        assert_eq!(
+            "0199".parse::<CtHex<[u8; 3]>>(),
+            Err(FromHexError::InvalidStringLength)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let json = serde_json::to_string(&CtHex([1_u8, 0x99, 0xff])).unwrap();
+        assert_eq!(json, r#""0199ff""#);
+        let hex: CtHex<Vec<u8>> = serde_json::from_str(r#""0199FF""#).unwrap();
+        assert_eq!(hex, CtHex(vec![1, 0x99, 0xff]));
+    }
+}
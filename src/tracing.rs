@@ -0,0 +1,102 @@
+//! Recording of hex wrappers as `tracing` fields.
+//!
+//! The wrappers can not implement `tracing::Value` directly, as that trait
+//! is sealed. Instead, `field` and `field_truncated` return values that
+//! `tracing` records through `Display`, so structured sinks like JSON layers
+//! see the plain hex string.
+//!
+//! ```rust
+//! use easy_hex::Hex;
+//!
+//! let payload = Hex(vec![0xab_u8; 1024]);
+//! tracing::info!(id = Hex([1_u8, 0x99, 0xff]).field(), payload = payload.field_truncated(4));
+//! ```
+//!
+//! Here `payload` is recorded as `abababab... (1024 bytes)`.
+
+use std::fmt;
+
+use ::tracing::field::{display, DisplayValue};
+
+use crate::{Hex, UpperHex};
+
+/// Hex string that is cut off after a maximum number of bytes.
+///
+/// If bytes are omitted, the formatted string ends with `...` and the total
+/// number of bytes.
+#[derive(Copy, Clone, Debug)]
+pub struct Truncated<H> {
+    hex: H,
+    max_len: usize,
+}
+
+macro_rules! impl_tracing {
+    ($Hex:ident) => {
+        impl<T> $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            /// Returns the value for recording as `tracing` field, which is
+            /// recorded as plain hex string.
+            pub fn field(&self) -> DisplayValue<&Self> {
+                display(self)
+            }
+
+            /// Returns the value for recording as `tracing` field, with at
+            /// most `max_len` bytes being encoded.
+            ///
+            /// This keeps log lines short for large buffers. See
+            /// [`Truncated`](crate::tracing::Truncated) for the format.
+            pub fn field_truncated(&self, max_len: usize) -> DisplayValue<Truncated<&Self>> {
+                display(Truncated { hex: self, max_len })
+            }
+        }
+
+        impl<T> fmt::Display for Truncated<&$Hex<T>>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                let bytes = self.hex.0.as_ref();
+                if bytes.len() <= self.max_len {
+                    fmt::Display::fmt(self.hex, f)
+                } else {
+                    let head = $Hex(&bytes[..self.max_len]);
+                    write!(f, "{}... ({} bytes)", head, bytes.len())
+                }
+            }
+        }
+    };
+}
+
+impl_tracing!(Hex);
+impl_tracing!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field() {
+        // NB: `tracing` records `DisplayValue` as `Debug` that forwards to
+        // `Display`, which is what sinks write out.
+        let hex = Hex([1_u8, 0x99, 0xff]);
+        assert_eq!(format!("{:?}", hex.field()), "0199ff");
+        assert_eq!(format!("{:?}", UpperHex(&hex.0[..]).field()), "0199FF");
+    }
+
+    #[test]
+    fn test_field_truncated() {
+        let hex = Hex([1_u8, 0x99, 0xff]);
+        assert_eq!(format!("{:?}", hex.field_truncated(3)), "0199ff");
+        assert_eq!(format!("{:?}", hex.field_truncated(8)), "0199ff");
+        assert_eq!(format!("{:?}", hex.field_truncated(2)), "0199... (3 bytes)");
+        assert_eq!(format!("{:?}", hex.field_truncated(0)), "... (3 bytes)");
+
+        let upper = UpperHex(vec![0xab_u8; 1024]);
+        assert_eq!(
+            format!("{:?}", upper.field_truncated(4)),
+            "ABABABAB... (1024 bytes)"
+        );
+    }
+}
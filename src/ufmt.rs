@@ -0,0 +1,66 @@
+use ::ufmt::{uDebug, uDisplay, uWrite, Formatter};
+
+use crate::{encode::chunked_serialize, Hex, UpperHex, LOWER, UPPER};
+
+macro_rules! impl_ufmt {
+    ($Hex:ident, $case:ident) => {
+        impl<T> uDisplay for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+            where
+                W: uWrite + ?Sized,
+            {
+                chunked_serialize::<_, $case>(self.0.as_ref(), |s| f.write_str(s))
+            }
+        }
+
+        impl<T> uDebug for $Hex<T>
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn fmt<W>(&self, f: &mut Formatter<'_, W>) -> Result<(), W::Error>
+            where
+                W: uWrite + ?Sized,
+            {
+                uDisplay::fmt(self, f)
+            }
+        }
+    };
+}
+
+impl_ufmt!(Hex, LOWER);
+impl_ufmt!(UpperHex, UPPER);
+
+#[cfg(test)]
+mod tests {
+    use ::ufmt::uwrite;
+
+    use super::*;
+
+    #[test]
+    fn test_ufmt() {
+        let mut s = String::new();
+        uwrite!(
+            s,
+            "{} {:?}",
+            Hex([1_u8, 0x99, 0xff]),
+            UpperHex(&[0xab_u8][..])
+        )
+        .unwrap();
+        assert_eq!(s, "0199ff AB");
+
+        let mut s = String::new();
+        uwrite!(s, "{}", UpperHex("")).unwrap();
+        assert_eq!(s, "");
+    }
+
+    #[test]
+    fn test_ufmt_large() {
+        let data: Vec<u8> = (0..=255).collect();
+        let mut s = String::new();
+        uwrite!(s, "{}", Hex(&data)).unwrap();
+        assert_eq!(s, Hex(&data).to_string());
+    }
+}
@@ -0,0 +1,105 @@
+//! Recording of hex wrappers with `valuable`.
+//!
+//! `valuable` can only record strings that are borrowed from the recorded
+//! value, so the wrappers are converted into a [`HexString`] first, which
+//! holds the encoded hex string and is recorded as plain string.
+//!
+//! ```rust
+//! use easy_hex::{valuable::HexString, Hex};
+//! use valuable::{Valuable, Value};
+//!
+//! let hex = HexString::from(&Hex([1_u8, 0x99, 0xff]));
+//! assert!(matches!(hex.as_value(), Value::String("0199ff")));
+//! ```
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use ::valuable::{Valuable, Value, Visit};
+
+use crate::{Hex, UpperHex};
+
+/// Encoded hex string of a wrapper, which implements `Valuable`.
+///
+/// The string has the letter case of the wrapper it was created from.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexString(String);
+
+impl HexString {
+    /// Returns the hex string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for HexString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl From<HexString> for String {
+    fn from(hex: HexString) -> Self {
+        hex.0
+    }
+}
+
+impl Valuable for HexString {
+    fn as_value(&self) -> Value<'_> {
+        Value::String(&self.0)
+    }
+
+    fn visit(&self, visit: &mut dyn Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+macro_rules! impl_valuable {
+    ($Hex:ident) => {
+        impl<T> From<&$Hex<T>> for HexString
+        where
+            T: AsRef<[u8]> + ?Sized,
+        {
+            fn from(hex: &$Hex<T>) -> Self {
+                HexString(hex.to_string())
+            }
+        }
+    };
+}
+
+impl_valuable!(Hex);
+impl_valuable!(UpperHex);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Strings(Vec<String>);
+
+    impl Visit for Strings {
+        fn visit_value(&mut self, value: Value<'_>) {
+            match value {
+                Value::String(s) => self.0.push(s.to_owned()),
+                value => panic!("unexpected value {value:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_valuable() {
+        let mut strings = Strings::default();
+        ::valuable::visit(&HexString::from(&Hex([1_u8, 0x99, 0xff])), &mut strings);
+        ::valuable::visit(&HexString::from(&UpperHex(&[0xab_u8][..])), &mut strings);
+        ::valuable::visit(&HexString::from(&Hex(Vec::<u8>::new())), &mut strings);
+        assert_eq!(strings.0, ["0199ff", "AB", ""]);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let hex = HexString::from(&UpperHex([1_u8, 0x99, 0xff]));
+        assert_eq!(hex.as_str(), "0199FF");
+        assert_eq!(hex.to_string(), "0199FF");
+        assert_eq!(String::from(hex), "0199FF");
+    }
+}
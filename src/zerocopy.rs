@@ -0,0 +1,37 @@
+// NB: the `zerocopy` traits are derived on the wrappers themselves, so casts
+// go through `FromBytes` and friends, and references to `T` are cast with
+// `From` or `HexExt` instead.
+
+#[cfg(test)]
+mod tests {
+    use ::zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout, Unaligned};
+
+    use crate::{Hex, UpperHex};
+
+    #[derive(FromBytes, IntoBytes, KnownLayout, Immutable, Unaligned)]
+    #[repr(C)]
+    struct Frame {
+        mac: Hex<[u8; 6]>,
+        payload: UpperHex<[u8; 2]>,
+    }
+
+    #[test]
+    fn test_derive() {
+        let bytes = [0, 1, 2, 3, 4, 5, 0xab, 0xcd];
+        let frame = Frame::ref_from_bytes(&bytes[..]).unwrap();
+        assert_eq!(frame.mac.to_string(), "000102030405");
+        assert_eq!(frame.payload.to_string(), "ABCD");
+        assert_eq!(frame.as_bytes(), bytes);
+
+        let hex = Hex::<[u8; 2]>::read_from_bytes(&bytes[6..]).unwrap();
+        assert_eq!(hex, Hex([0xab, 0xcd]));
+    }
+
+    #[test]
+    fn test_unsized() {
+        let bytes = [1_u8, 0x99, 0xff];
+        let hex: &Hex<[u8]> = bytes[..].into();
+        assert_eq!(hex.to_string(), "0199ff");
+        assert_eq!(hex.as_bytes(), bytes);
+    }
+}
@@ -0,0 +1,83 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn run(args: &[&str], stdin: &[u8]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_easy-hex"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(args: &[&str], stdin: &[u8]) -> Vec<u8> {
+    let output = run(args, stdin);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    output.stdout
+}
+
+#[test]
+fn test_encode() {
+    assert_eq!(stdout(&["encode"], &[1, 0x99, 0xff]), b"0199ff\n");
+    assert_eq!(
+        stdout(&["encode", "--upper"], &[1, 0x99, 0xff]),
+        b"0199FF\n"
+    );
+    assert_eq!(stdout(&["encode"], &[]), b"");
+    assert_eq!(
+        stdout(
+            &["encode", "-p", "0x", "-s", ", ", "-w", "2"],
+            &[1, 0x99, 0xff]
+        ),
+        b"0x01, 0x99\n0xff\n"
+    );
+}
+
+#[test]
+fn test_decode() {
+    assert_eq!(stdout(&["decode"], b"0199ff\n"), [1, 0x99, 0xff]);
+    assert_eq!(stdout(&["decode"], b"0x01, 0x99\n0xFF\n"), [1, 0x99, 0xff]);
+
+    let output = run(&["decode"], b"019");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Odd number of digits"));
+
+    let output = run(&["decode"], b"01zz");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("'z'"));
+}
+
+#[test]
+fn test_dump() {
+    assert_eq!(
+        stdout(&["dump"], b"hello world\n"),
+        b"00000000: 6865 6c6c 6f20 776f 726c 640a            hello world.\n"
+    );
+}
+
+#[test]
+fn test_roundtrip_file() {
+    let data: Vec<u8> = (0..=255).cycle().take(100_000).collect();
+    let path = std::env::temp_dir().join(format!("easy-hex-cli-{}.bin", std::process::id()));
+    std::fs::write(&path, &data).unwrap();
+
+    let hex = stdout(&["encode", "-w", "32", path.to_str().unwrap()], &[]);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(stdout(&["decode", "-"], &hex), data);
+}
+
+#[test]
+fn test_missing_file() {
+    let output = run(&["encode", "does/not/exist"], &[]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("easy-hex: "));
+}